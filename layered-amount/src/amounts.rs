@@ -1,4 +1,4 @@
-use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use layered_nlp::{to_ascii_digits, x, LLCursorAssignment, LLSelection, Resolver, TextTag};
 use rust_decimal::Decimal;
 
 #[derive(Clone, Debug)]
@@ -65,7 +65,8 @@ impl Resolver for AmountResolver {
         while let Some((mut selection, (_, text))) =
             search_range_sel.find_first_by(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
         {
-            let mut number_string = String::from(text);
+            let mut number_string = String::new();
+            push_digits(&mut number_string, text);
             let mut last_valid_selection = None;
 
             // Avoid trailing delimeters
@@ -82,7 +83,7 @@ impl Resolver for AmountResolver {
                 if let Some((following_delimeter_sel, (_, text))) = selection
                     .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
                 {
-                    number_string.push_str(text);
+                    push_digits(&mut number_string, text);
                     last_valid_selection = None;
                     selection = following_delimeter_sel;
                 } else {
@@ -103,7 +104,7 @@ impl Resolver for AmountResolver {
                 if let Some((following_decimal_sel, ((), text))) = selection
                     .match_first_forwards(&x::all((x::attr_eq(&TextTag::NATN), x::token_text())))
                 {
-                    number_string.push_str(text);
+                    push_digits(&mut number_string, text);
                     last_valid_selection = None;
                     selection = following_decimal_sel;
                 }
@@ -125,3 +126,12 @@ impl Resolver for AmountResolver {
         attrs
     }
 }
+
+/// Digits of [TextTag::NATN] tokens may come from any script (`١٢٣`, `１２３`),
+/// but [Decimal] only parses ascii digits.
+fn push_digits(number_string: &mut String, natn_text: &str) {
+    match to_ascii_digits(natn_text) {
+        Some(ascii_digits) => number_string.push_str(&ascii_digits),
+        None => number_string.push_str(natn_text),
+    }
+}
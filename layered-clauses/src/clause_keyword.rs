@@ -14,6 +14,7 @@ pub struct ClauseKeywordResolver {
     cond_start: Vec<&'static str>,
     and: Vec<&'static str>,
     then: Vec<&'static str>,
+    find_texts: x::FindTexts,
 }
 
 impl ClauseKeywordResolver {
@@ -22,15 +23,23 @@ impl ClauseKeywordResolver {
             cond_start: cond_start.to_vec(),
             and: and.to_vec(),
             then: then.to_vec(),
+            find_texts: x::token_texts,
         }
     }
+
+    /// Compare the keywords with the text of `A` attributes instead of the token text,
+    /// like [Normalized](layered_nlp::Normalized) or a lemma, so `"rains"` can match `"rain"`.
+    pub fn match_on<A: AsRef<str> + 'static>(mut self) -> Self {
+        self.find_texts = x::attr_texts::<A>;
+        self
+    }
 }
 
 impl Resolver for ClauseKeywordResolver {
     type Attr = ClauseKeyword;
 
     fn go(&self, sel: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
-        sel.find_by(&x::token_text())
+        (self.find_texts)(&sel)
             .into_iter()
             .flat_map(|(sel, text)| {
                 let text = text.to_lowercase();
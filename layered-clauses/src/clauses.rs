@@ -1,5 +1,5 @@
 use super::ClauseKeyword;
-use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, Sentence, TextTag};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Clause {
@@ -18,6 +18,21 @@ impl Resolver for ClauseResolver {
     type Attr = Clause;
 
     fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        // conditions don't carry over to the next sentence
+        let sentences = selection.find_by(&x::attr::<Sentence>());
+        if sentences.is_empty() {
+            self.sentence_clauses(selection)
+        } else {
+            sentences
+                .into_iter()
+                .flat_map(|(sentence, _)| self.sentence_clauses(sentence))
+                .collect()
+        }
+    }
+}
+
+impl ClauseResolver {
+    fn sentence_clauses(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Clause>> {
         let mut condition_clause_found = false;
 
         let clauses = selection.split_by(&x::any_of((
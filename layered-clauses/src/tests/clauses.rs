@@ -1,5 +1,7 @@
 use crate::{Clause, ClauseKeyword, ClauseKeywordResolver, ClauseResolver};
-use layered_nlp::{create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay};
+use layered_nlp::{
+    create_line_from_input_tokens, InputToken, LLLine, LLLineDisplay, Sentence, SentenceResolver,
+};
 use layered_part_of_speech::{POSTagResolver, Tag};
 
 fn test_setup(sentence: &'static str) -> LLLine {
@@ -23,7 +25,7 @@ fn test_clauses() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     When     it     rains  ,     then     it     pours  .
     ╰──╯ConditionStart
                                  ╰──╯Then
@@ -46,7 +48,7 @@ fn test_short_clauses() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     When     it     rains  ,     run  !
     ╰──╯ConditionStart
              ╰──────────╯Condition
@@ -70,7 +72,7 @@ fn test_clauses_comma() {
     ll_line_display.include::<Clause>();
     ll_line_display.include::<Tag>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     If     it     is     raining  ,     open     your     umbrella  .
     ╰╯ConditionStart
            ╰───────────────────╯Condition
@@ -105,7 +107,7 @@ fn tired() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Si     tu     es     fatigué  ,     va     te     coucher  .
     ╰╯ConditionStart
            ╰───────────────────╯Condition
@@ -123,7 +125,7 @@ fn tired_rev() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Va     te     coucher     si     tu     es     fatigué  .
                               ╰╯ConditionStart
     ╰───────────────────╯LeadingEffect
@@ -146,7 +148,7 @@ fn rain() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     If     it     is     raining     then     open     the     umbrella     and     close     the     garage     and     the     door  .
     ╰╯ConditionStart
                                      ╰──╯Then
@@ -175,7 +177,7 @@ fn rain_rev() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Open     the     umbrella     and     close     the     garage     and     the     door     if     it     is     raining  .
                                   ╰─╯And
                                                                        ╰─╯And
@@ -202,7 +204,7 @@ fn extra_rain() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Open     the     umbrella     if     it     is     raining     and     not     too     windy  .
                                   ╰╯ConditionStart
                                                                    ╰─╯And
@@ -227,9 +229,34 @@ fn no_keyword() {
     ll_line_display.include::<ClauseKeyword>();
     ll_line_display.include::<Clause>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     Open     the     umbrella  .
     ╰───────────────────────╯Independent
     "###
     );
 }
+
+#[test]
+fn test_clauses_per_sentence() {
+    let ll_line = test_setup("When it rains, then it pours. Stay inside.")
+        .run(&SentenceResolver::default())
+        .run(&ClauseKeywordResolver::new(
+            &["if", "when"],
+            &["and"],
+            &["then"],
+        ))
+        .run(&ClauseResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Sentence>();
+    ll_line_display.include::<Clause>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    When     it     rains  ,     then     it     pours  .     Stay     inside  .
+    ╰───────────────────────────────────────────────────╯Sentence
+                                                              ╰────────────────╯Sentence
+             ╰──────────╯Condition
+                                          ╰──────────╯TrailingEffect
+                                                              ╰─────────────╯Independent
+    "###);
+}
@@ -0,0 +1,153 @@
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/storyscript/layered-nlp/main/assets/layered-nlp.svg",
+    issue_tracker_base_url = "https://github.com/storyscript/layered-nlp/issues/"
+)]
+
+use std::collections::HashMap;
+
+use layered_nlp::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+pub use rust_stemmers::Algorithm;
+use rust_stemmers::Stemmer;
+
+/// Irregular English forms the stemmer can't reduce
+const ENGLISH_IRREGULAR: &[(&str, &str)] = &[
+    ("am", "be"),
+    ("are", "be"),
+    ("is", "be"),
+    ("was", "be"),
+    ("were", "be"),
+    ("been", "be"),
+    ("has", "have"),
+    ("had", "have"),
+    ("did", "do"),
+    ("does", "do"),
+    ("done", "do"),
+    ("went", "go"),
+    ("gone", "go"),
+    ("ran", "run"),
+    ("came", "come"),
+    ("saw", "see"),
+    ("seen", "see"),
+    ("took", "take"),
+    ("taken", "take"),
+    ("gave", "give"),
+    ("given", "give"),
+    ("made", "make"),
+    ("said", "say"),
+    ("got", "get"),
+    ("gotten", "get"),
+    ("knew", "know"),
+    ("known", "know"),
+    ("thought", "think"),
+    ("bought", "buy"),
+    ("brought", "bring"),
+    ("paid", "pay"),
+    ("sent", "send"),
+    ("spent", "spend"),
+    ("left", "leave"),
+    ("felt", "feel"),
+    ("kept", "keep"),
+    ("told", "tell"),
+    ("found", "find"),
+    ("wrote", "write"),
+    ("written", "write"),
+    ("better", "good"),
+    ("best", "good"),
+    ("worse", "bad"),
+    ("worst", "bad"),
+    ("children", "child"),
+    ("men", "man"),
+    ("women", "woman"),
+    ("people", "person"),
+    ("feet", "foot"),
+    ("teeth", "tooth"),
+    ("mice", "mouse"),
+    ("geese", "goose"),
+];
+
+/// Dictionary form of a [TextTag::WORD] token, or its stem when the word isn't in the dictionary.
+///
+/// Always lowercase. Match it with [x::attr_text::<Lemma>()](layered_nlp::x::attr_text), or use
+/// it with the `match_on` option of resolvers like `TextMatchAssignResolver`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Lemma(pub String);
+
+impl Lemma {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Lemma {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Assigns a [Lemma] to [TextTag::WORD] tokens.
+///
+/// Words are looked up in a dictionary of forms first, other words are reduced with a Snowball stemmer.
+/// Stems aren't always words (`happily` becomes `happili`), so lookup keys should go through
+/// [LemmaResolver::lemmatize] as well.
+pub struct LemmaResolver {
+    dictionary: HashMap<String, String>,
+    stemmer: Stemmer,
+}
+
+impl Default for LemmaResolver {
+    /// English stemmer, with a dictionary of common irregular forms
+    fn default() -> Self {
+        LemmaResolver::new(Algorithm::English).with_dictionary(ENGLISH_IRREGULAR.iter().copied())
+    }
+}
+
+impl LemmaResolver {
+    /// Resolver using the Snowball stemmer for `algorithm`, with an empty dictionary
+    pub fn new(algorithm: Algorithm) -> Self {
+        LemmaResolver {
+            dictionary: HashMap::new(),
+            stemmer: Stemmer::create(algorithm),
+        }
+    }
+
+    /// Add `(form, lemma)` pairs to the dictionary, forms are compared case insensitively
+    pub fn with_dictionary<I, F, L>(mut self, dictionary: I) -> Self
+    where
+        I: IntoIterator<Item = (F, L)>,
+        F: AsRef<str>,
+        L: Into<String>,
+    {
+        self.dictionary.extend(
+            dictionary
+                .into_iter()
+                .map(|(form, lemma)| (form.as_ref().to_lowercase(), lemma.into())),
+        );
+        self
+    }
+
+    /// The lemma the resolver assigns to `word`
+    pub fn lemmatize(&self, word: &str) -> String {
+        let word = word.to_lowercase();
+        match self.dictionary.get(&word) {
+            Some(lemma) => lemma.clone(),
+            None => self.stemmer.stem(&word).into_owned(),
+        }
+    }
+}
+
+impl Resolver for LemmaResolver {
+    type Attr = Lemma;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .map(|(selection, (_, word))| selection.finish_with_attr(Lemma(self.lemmatize(word))))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod lemma;
+}
@@ -0,0 +1,71 @@
+use crate::{Algorithm, Lemma, LemmaResolver};
+use layered_clauses::{ClauseKeyword, ClauseKeywordResolver};
+use layered_nlp::{create_line_from_string, LLLineDisplay, TextMatchAssignResolver};
+
+#[test]
+fn test_lemmatize() {
+    let resolver = LemmaResolver::default();
+    assert_eq!(resolver.lemmatize("rains"), "rain");
+    assert_eq!(resolver.lemmatize("Rained"), "rain");
+    assert_eq!(resolver.lemmatize("raining"), "rain");
+    assert_eq!(resolver.lemmatize("went"), "go");
+    assert_eq!(resolver.lemmatize("Children"), "child");
+
+    let resolver = LemmaResolver::new(Algorithm::English);
+    assert_eq!(resolver.lemmatize("went"), "went");
+
+    let resolver = LemmaResolver::new(Algorithm::French).with_dictionary([("yeux", "œil")]);
+    assert_eq!(resolver.lemmatize("chantaient"), "chant");
+    assert_eq!(resolver.lemmatize("Yeux"), "œil");
+}
+
+#[test]
+fn test_lemmas() {
+    let ll_line =
+        create_line_from_string("It rained, the children went home").run(&LemmaResolver::default());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Lemma>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    It     rained  ,     the     children     went     home
+    ╰╯Lemma("it")
+           ╰────╯Lemma("rain")
+                         ╰─╯Lemma("the")
+                                 ╰──────╯Lemma("child")
+                                              ╰──╯Lemma("go")
+                                                       ╰──╯Lemma("home")
+    "###);
+}
+
+#[test]
+fn test_match_on_lemma() {
+    #[derive(Debug, Clone)]
+    struct Weather;
+
+    let ll_line =
+        create_line_from_string("When it rains, then it pours. It rained and it's raining")
+            .run(&LemmaResolver::default())
+            .run(
+                &TextMatchAssignResolver::new_case_insensitive_str_arr([("rain", Weather)])
+                    .match_on::<Lemma>(),
+            )
+            .run(
+                &ClauseKeywordResolver::new(&["if", "when"], &["and"], &["then"])
+                    .match_on::<Lemma>(),
+            );
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Weather>();
+    ll_line_display.include::<ClauseKeyword>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    When     it     rains  ,     then     it     pours  .     It     rained     and     it's     raining
+                    ╰───╯Weather
+                                                                     ╰────╯Weather
+                                                                                                 ╰─────╯Weather
+    ╰──╯ConditionStart
+                                 ╰──╯Then
+                                                                                ╰─╯And
+    "###);
+}
@@ -0,0 +1,180 @@
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/storyscript/layered-nlp/main/assets/layered-nlp.svg",
+    issue_tracker_base_url = "https://github.com/storyscript/layered-nlp/issues/"
+)]
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use layered_nlp::x::{self, Pattern, PatternError, PatternRegistry};
+use layered_nlp::{LLCursorAssignment, LLSelection, Resolver};
+use serde::Deserialize;
+
+/// Attribute assigned by a [RuleResolver], named after the `label` of the rule which matched.
+///
+/// `fields` hold the text of each `name:` capture of the rule pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub name: String,
+    pub fields: BTreeMap<String, String>,
+}
+
+impl Label {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    rules: Vec<RuleDefinition>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleDefinition {
+    name: String,
+    pattern: String,
+    label: String,
+}
+
+struct Rule {
+    name: String,
+    pattern: Pattern,
+    label: String,
+}
+
+/// Error from [RuleResolver::from_toml] and [RuleResolver::from_json]
+#[derive(Debug)]
+pub enum RuleError {
+    Toml(toml::de::Error),
+    Json(serde_json::Error),
+    /// The pattern of the rule named `rule` couldn't be parsed
+    Pattern {
+        rule: String,
+        error: PatternError,
+    },
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::Toml(error) => write!(f, "Invalid TOML rule file: {}", error),
+            RuleError::Json(error) => write!(f, "Invalid JSON rule file: {}", error),
+            RuleError::Pattern { rule, error } => write!(f, "In rule `{}`: {}", rule, error),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RuleError::Toml(error) => Some(error),
+            RuleError::Json(error) => Some(error),
+            RuleError::Pattern { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Assigns a [Label] wherever the pattern of one of its rules matches.
+///
+/// Rules are read from TOML or JSON, with [x::Pattern] patterns referring to the attributes
+/// of a [PatternRegistry]:
+///
+/// ```toml
+/// [[rules]]
+/// name = "pay amount"
+/// pattern = '"pay" ( "$" | "€" )? amount:[NATN]'
+/// label = "Payment"
+/// ```
+pub struct RuleResolver {
+    rules: Vec<Rule>,
+}
+
+impl RuleResolver {
+    pub fn from_toml(source: &str, registry: &PatternRegistry) -> Result<Self, RuleError> {
+        let file: RuleFile = toml::from_str(source).map_err(RuleError::Toml)?;
+        RuleResolver::from_definitions(file.rules, registry)
+    }
+
+    /// Same format as [RuleResolver::from_toml], like `{ "rules": [{ "name": ..., "pattern": ..., "label": ... }] }`
+    pub fn from_json(source: &str, registry: &PatternRegistry) -> Result<Self, RuleError> {
+        let file: RuleFile = serde_json::from_str(source).map_err(RuleError::Json)?;
+        RuleResolver::from_definitions(file.rules, registry)
+    }
+
+    fn from_definitions(
+        definitions: Vec<RuleDefinition>,
+        registry: &PatternRegistry,
+    ) -> Result<Self, RuleError> {
+        let rules = definitions
+            .into_iter()
+            .map(
+                |definition| match Pattern::parse(&definition.pattern, registry) {
+                    Ok(pattern) => Ok(Rule {
+                        name: definition.name,
+                        pattern,
+                        label: definition.label,
+                    }),
+                    Err(error) => Err(RuleError::Pattern {
+                        rule: definition.name,
+                        error,
+                    }),
+                },
+            )
+            .collect::<Result<_, _>>()?;
+
+        Ok(RuleResolver { rules })
+    }
+
+    /// Names of the rules, in file order
+    pub fn rule_names(&self) -> impl Iterator<Item = &str> {
+        self.rules.iter().map(|rule| rule.name.as_str())
+    }
+}
+
+fn selection_text(selection: &LLSelection) -> String {
+    selection
+        .find_by(&x::token_text())
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect()
+}
+
+impl Resolver for RuleResolver {
+    type Attr = Label;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut labels: Vec<(LLSelection, Label)> = Vec::new();
+
+        for rule in &self.rules {
+            for (found, captures) in selection.find_captures(&rule.pattern) {
+                let fields = captures
+                    .names()
+                    .filter_map(|name| {
+                        let sub_selection = captures.selection(name)?;
+                        Some((name.to_string(), selection_text(&sub_selection)))
+                    })
+                    .collect();
+                let label = Label {
+                    name: rule.label.clone(),
+                    fields,
+                };
+                if !labels.contains(&(found.clone(), label.clone())) {
+                    labels.push((found, label));
+                }
+            }
+        }
+
+        labels
+            .into_iter()
+            .map(|(found, label)| found.finish_with_attr(label))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    mod rules;
+}
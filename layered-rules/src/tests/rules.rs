@@ -0,0 +1,113 @@
+use crate::{Label, RuleResolver};
+use layered_nlp::x::PatternRegistry;
+use layered_nlp::{create_line_from_string, LLLineDisplay, TextMatchAssignResolver};
+
+const RULES_TOML: &str = r#"
+[[rules]]
+name = "pay amount"
+pattern = '"pay" ( "$" | "€" )? amount:[NATN]'
+label = "Payment"
+
+[[rules]]
+name = "greeting"
+pattern = '( "hi" | "hello" ) who:[WORD]'
+label = "Greeting"
+"#;
+
+#[test]
+fn test_toml_rules() {
+    let resolver = RuleResolver::from_toml(RULES_TOML, &PatternRegistry::default()).unwrap();
+    assert_eq!(
+        resolver.rule_names().collect::<Vec<_>>(),
+        ["pay amount", "greeting"]
+    );
+
+    let ll_line = create_line_from_string("Hello Anna, please pay $ 40 today").run(&resolver);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Label>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Hello     Anna  ,     please     pay     $     40     today
+                                     ╰──────────────╯Label { name: "Payment", fields: {"amount": "40"} }
+    ╰────────────╯Label { name: "Greeting", fields: {"who": "Anna"} }
+    "###);
+}
+
+#[test]
+fn test_json_rules() {
+    let resolver = RuleResolver::from_json(
+        r#"{ "rules": [{ "name": "pay", "pattern": "\"pay\" amount:[NATN]", "label": "Payment" }] }"#,
+        &PatternRegistry::default(),
+    )
+    .unwrap();
+
+    let ll_line = create_line_from_string("pay 40 or PAY 50").run(&resolver);
+    let labels = ll_line
+        .find(&layered_nlp::x::attr::<Label>())
+        .into_iter()
+        .map(|found| found.attr().field("amount").unwrap().to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(labels, ["40", "50"]);
+}
+
+#[test]
+fn test_registered_attribute() {
+    #[derive(Debug, Clone)]
+    struct Service;
+
+    let registry = PatternRegistry::default().register::<Service>("Service");
+    let resolver = RuleResolver::from_toml(
+        r#"
+        [[rules]]
+        name = "subscription"
+        pattern = 'service:[Service] "subscription"'
+        label = "Subscription"
+        "#,
+        &registry,
+    )
+    .unwrap();
+
+    let ll_line = create_line_from_string("Cancel the Slack subscription")
+        .run(&TextMatchAssignResolver::new_case_insensitive_str_arr([(
+            "Slack", Service,
+        )]))
+        .run(&resolver);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Label>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Cancel     the     Slack     subscription
+                       ╰────────────────────╯Label { name: "Subscription", fields: {"service": "Slack"} }
+    "###);
+}
+
+#[test]
+fn test_rule_errors() {
+    let error = RuleResolver::from_toml(
+        r#"
+        [[rules]]
+        name = "broken"
+        pattern = '"pay" [Amount]'
+        label = "Payment"
+        "#,
+        &PatternRegistry::default(),
+    )
+    .err()
+    .unwrap();
+    insta::assert_snapshot!(error, @r###"
+    In rule `broken`: Unknown attribute `Amount` at column 7
+    "pay" [Amount]
+          ^^^^^^^^
+    "###);
+
+    let error = RuleResolver::from_json(
+        r#"{ "rules": [{ "name": "pay" }] }"#,
+        &PatternRegistry::default(),
+    )
+    .err()
+    .unwrap();
+    insta::assert_snapshot!(error, @"Invalid JSON rule file: missing field `pattern` at line 1 column 29");
+}
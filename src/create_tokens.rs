@@ -1,8 +1,10 @@
-mod get_word_tag;
+mod decimal_digit;
 
-use crate::ll_line::{LLLine, LLToken, LToken, TextTag};
+pub use decimal_digit::{decimal_digit_value, to_ascii_digits};
+
+use crate::ll_line::{LLLine, LLToken, LToken, TextPositions, ValueToken};
+use crate::tokenizers::{DefaultTokenizer, Tokenizer};
 use crate::type_bucket::AnyAttribute;
-use unicode_segmentation::UnicodeSegmentation;
 
 pub enum InputToken {
     Text {
@@ -14,6 +16,8 @@ pub enum InputToken {
     Custom {
         /// Position relative size
         size: usize,
+        /// Label and payload of the [LToken::Value] created for this token
+        value: ValueToken,
         /// Custom attributes
         attrs: Vec<AnyAttribute>,
     },
@@ -25,7 +29,27 @@ impl InputToken {
     }
 
     pub fn custom(size: usize, attrs: Vec<AnyAttribute>) -> Self {
-        InputToken::Custom { size, attrs }
+        InputToken::Custom {
+            size,
+            value: ValueToken::default(),
+            attrs,
+        }
+    }
+
+    /// Custom token carrying a `payload`, which can be matched with [x::value](crate::x::value).
+    ///
+    /// `label` is shown in place of the token by [LLLineDisplay](crate::LLLineDisplay).
+    pub fn value<T: 'static + std::fmt::Debug + Send + Sync>(
+        size: usize,
+        label: impl Into<String>,
+        payload: T,
+        attrs: Vec<AnyAttribute>,
+    ) -> Self {
+        InputToken::Custom {
+            size,
+            value: ValueToken::new(label).with_payload(payload),
+            attrs,
+        }
     }
 
     pub fn add_attr<T: 'static + std::fmt::Debug + Send + Sync>(&mut self, value: T) {
@@ -36,6 +60,37 @@ impl InputToken {
     }
 }
 
+/// Reasons for [try_create_line_from_input_tokens] to refuse creating a line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CreateLineError {
+    /// The [InputToken::Text] at `input_idx` has an empty `text`.
+    EmptyInput { input_idx: usize },
+    /// The [Tokenizer]'s pieces for the text of the [InputToken::Text] at `input_idx` aren't
+    /// the text split up in order, starting from the byte `offset` of the text.
+    TokenizerMismatch { input_idx: usize, offset: usize },
+}
+
+impl std::fmt::Display for CreateLineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CreateLineError::EmptyInput { input_idx } => {
+                write!(
+                    f,
+                    "Cannot create a LLLine from empty String (input token {})",
+                    input_idx
+                )
+            }
+            CreateLineError::TokenizerMismatch { input_idx, offset } => write!(
+                f,
+                "Cannot create a LLLine from tokens not matching the text from byte {} (input token {})",
+                offset, input_idx
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CreateLineError {}
+
 /// Splits the `InputToken`s and generate `TextTag`s.
 #[deprecated = "Use create_line_from_input_tokens"]
 pub fn create_tokens<F>(input: Vec<InputToken>, get_text_size: F) -> LLLine
@@ -45,36 +100,107 @@ where
     create_line_from_input_tokens(input, get_text_size)
 }
 
+/// Splits the `InputToken`s, generates `TextTag`s and creates a new [LLLine].
+///
+/// # Panics
+///
+/// Panics if the input can't be turned into a line, see [try_create_line_from_input_tokens]
+/// for a version which returns a [CreateLineError] instead.
 pub fn create_line_from_input_tokens<F>(input: Vec<InputToken>, get_text_size: F) -> LLLine
 where
     F: Fn(&str) -> usize,
+{
+    match try_create_line_from_input_tokens(input, get_text_size) {
+        Ok(ll_line) => ll_line,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Fallible version of [create_line_from_input_tokens], useful when the input comes from users.
+pub fn try_create_line_from_input_tokens<F>(
+    input: Vec<InputToken>,
+    get_text_size: F,
+) -> Result<LLLine, CreateLineError>
+where
+    F: Fn(&str) -> usize,
+{
+    try_create_line_from_input_tokens_with_tokenizer(
+        input,
+        &DefaultTokenizer::default(),
+        get_text_size,
+    )
+}
+
+/// Same as [create_line_from_input_tokens] but splits text with the given [Tokenizer].
+///
+/// # Panics
+///
+/// Panics if the input can't be turned into a line, see [try_create_line_from_input_tokens_with_tokenizer]
+/// for a version which returns a [CreateLineError] instead.
+pub fn create_line_from_input_tokens_with_tokenizer<T, F>(
+    input: Vec<InputToken>,
+    tokenizer: &T,
+    get_text_size: F,
+) -> LLLine
+where
+    T: Tokenizer + ?Sized,
+    F: Fn(&str) -> usize,
+{
+    match try_create_line_from_input_tokens_with_tokenizer(input, tokenizer, get_text_size) {
+        Ok(ll_line) => ll_line,
+        Err(err) => panic!("{}", err),
+    }
+}
+
+/// Fallible version of [create_line_from_input_tokens_with_tokenizer].
+pub fn try_create_line_from_input_tokens_with_tokenizer<T, F>(
+    input: Vec<InputToken>,
+    tokenizer: &T,
+    get_text_size: F,
+) -> Result<LLLine, CreateLineError>
+where
+    T: Tokenizer + ?Sized,
+    F: Fn(&str) -> usize,
 {
     let mut start_idx_end_idx_attributes: Vec<(usize, usize, Vec<AnyAttribute>)> = Vec::new();
     let mut lltokens: Vec<LLToken> = Vec::new();
     let mut current_size = 0;
+    let mut current_text = TextPositions::default();
 
-    for (ltokens, attrs) in input.into_iter().map(|input_token| match input_token {
-        InputToken::Text { text, attrs } => {
-            (create_tokens_for_string(&text, &get_text_size), attrs)
-        }
-        InputToken::Custom { size, attrs } => (vec![(LToken::Value, size)], attrs),
-    }) {
-        assert!(
-            !ltokens.is_empty(),
-            "Cannot create a LLLine from empty String"
-        );
+    for (input_idx, input_token) in input.into_iter().enumerate() {
+        let (ltokens, attrs) = match input_token {
+            InputToken::Text { text, attrs } => {
+                if text.is_empty() {
+                    return Err(CreateLineError::EmptyInput { input_idx });
+                }
+
+                let ltokens = tokenize_text(input_idx, text, tokenizer, &get_text_size)?;
+
+                (ltokens, attrs)
+            }
+            InputToken::Custom { size, value, attrs } => {
+                (vec![(LToken::Value(value), size)], attrs)
+            }
+        };
 
         let from_idx = lltokens.len();
         for (ltoken, size) in ltokens {
             let next_size = current_size + size;
+            let next_text = match &ltoken {
+                LToken::Text(text, _) => current_text.add(TextPositions::of(text)),
+                LToken::Value(_) => current_text,
+            };
             lltokens.push(LLToken {
                 token_idx: lltokens.len(),
                 pos_starts_at: current_size,
                 pos_ends_at: next_size,
+                text_starts_at: current_text,
+                text_ends_at: next_text,
                 token: ltoken,
             });
 
             current_size = next_size;
+            current_text = next_text;
         }
         let to_idx = lltokens.len() - 1;
 
@@ -86,126 +212,46 @@ where
         ll_line.add_any_attrs(start_idx, end_idx, attributes);
     }
 
-    ll_line
+    Ok(ll_line)
 }
 
-// helper for [create_tokens]
-fn create_tokens_for_string<F>(input: &str, get_text_size: F) -> Vec<(LToken, usize)>
+/// Split the `text` of the input token at `input_idx` into tokens with their size.
+pub(crate) fn tokenize_text<T, F>(
+    input_idx: usize,
+    text: String,
+    tokenizer: &T,
+    get_text_size: F,
+) -> Result<Vec<(LToken, usize)>, CreateLineError>
 where
+    T: Tokenizer + ?Sized,
     F: Fn(&str) -> usize,
 {
-    // `fold` because we end up splitting more than just unicode word boundaries
-    input
-        .split_word_bounds()
-        .fold(Vec::new(), |mut ltokens, unicode_word| {
-            // Split apart digit word boundaries, because unicode `split_word_bounds` will group digits and commas and points together
-            // such as "12,3" and "10.0". We need these to be split up further into ["12", ",", "3"] and ["10", ".", "0"] respectively.
-            // http://www.unicode.org/reports/tr29/#Word_Boundaries
-            // if \d+[,\.a-zA-Z]\d+ or more repeats (3 is minimum)
-            if unicode_word.chars().next().unwrap().is_ascii_digit() {
-                // make assumtions about the length of every char being 1
-                assert!(
-                    unicode_word.is_ascii(),
-                    "Unexpected non-ascii digit word boundary: {}",
-                    unicode_word
-                );
-
-                let mut collected_digits = String::new();
-                let mut collected_letters = String::new();
-
-                // using a macro since pulling this out into a closure or function would be very verbose
-                // as you'd have to pass references to collected_digits, get_text_size, ltokens
-                macro_rules! insert_collected_digits {
-                    () => {
-                        if collected_digits.len() > 0 {
-                            let size = get_text_size(&collected_digits);
-                            ltokens.push((
-                                LToken::Text(std::mem::take(&mut collected_digits), TextTag::NATN),
-                                size,
-                            ));
-                        }
-                    };
-                }
+    let pieces = tokenizer.tokenize(&text);
 
-                macro_rules! insert_collected_letters {
-                    () => {
-                        if collected_letters.len() > 0 {
-                            let size = get_text_size(&collected_letters);
-                            ltokens.push((
-                                LToken::Text(std::mem::take(&mut collected_letters), TextTag::WORD),
-                                size,
-                            ));
-                        }
-                    };
-                }
+    let mut ltokens = Vec::new();
+    let mut offset = 0;
+    for (piece, tag) in pieces {
+        if !text[offset..].starts_with(piece) {
+            return Err(CreateLineError::TokenizerMismatch { input_idx, offset });
+        }
+        offset += piece.len();
 
-                for ch in unicode_word.chars() {
-                    if ch.is_ascii_digit() {
-                        insert_collected_letters!();
-                        collected_digits.push(ch);
-                    } else if ch.is_alphabetic() {
-                        insert_collected_digits!();
-                        collected_letters.push(ch);
-                    } else {
-                        insert_collected_letters!();
-                        insert_collected_digits!();
-                        let text = String::from(ch);
-                        let size = get_text_size(&text);
-                        ltokens.push((LToken::Text(text, TextTag::PUNC), size));
-                    }
-                }
+        ltokens.push((LToken::Text(piece.to_string(), tag), get_text_size(piece)));
+    }
 
-                insert_collected_letters!();
-                insert_collected_digits!();
-            } else {
-                // Hmm
-                // This was supposed to catch quotes with apostrophes
-                // But din't work that well and split clauses on apostrophes which is more common
-                let last_apostrophe_index = 0;
-
-                // unicode_word.match_indices('\'').for_each(|(index, _)| {
-                //     if !(last_apostrophe_index..index).is_empty() {
-                //         ltokens.push((
-                //             LToken::Text(
-                //                 unicode_word[last_apostrophe_index..index].to_string(),
-                //                 get_word_tag::get_unicode_word_tag(
-                //                     &unicode_word[last_apostrophe_index..index],
-                //                 ),
-                //             ),
-                //             get_text_size(&unicode_word[last_apostrophe_index..index]),
-                //         ));
-                //     }
-
-                //     if last_apostrophe_index == 0 {
-                //         ltokens.push((
-                //             LToken::Text("'".to_string(), get_word_tag::get_unicode_word_tag("'")),
-                //             get_text_size("'"),
-                //         ));
-                //     }
-
-                //     last_apostrophe_index = index + 1;
-                // });
-
-                if !unicode_word[last_apostrophe_index..].is_empty() {
-                    ltokens.push((
-                        LToken::Text(
-                            unicode_word[last_apostrophe_index..].to_string(),
-                            get_word_tag::get_unicode_word_tag(
-                                &unicode_word[last_apostrophe_index..],
-                            ),
-                        ),
-                        get_text_size(&unicode_word[last_apostrophe_index..]),
-                    ));
-                }
-            }
+    if offset != text.len() {
+        return Err(CreateLineError::TokenizerMismatch { input_idx, offset });
+    }
 
-            ltokens
-        })
+    Ok(ltokens)
 }
 
 #[cfg(test)]
 mod test {
-    use super::{create_line_from_input_tokens, InputToken};
+    use super::{
+        create_line_from_input_tokens, try_create_line_from_input_tokens, CreateLineError,
+        InputToken,
+    };
     use crate::ll_line::LLLineDisplay;
     use crate::type_bucket::AnyAttribute;
 
@@ -216,6 +262,7 @@ mod test {
     }
 
     #[derive(Debug, Clone)]
+    #[allow(dead_code)]
     struct Link {
         href: String,
     }
@@ -246,7 +293,7 @@ mod test {
         let mut ll_line_display = LLLineDisplay::new(&ll_line);
         ll_line_display.include::<MarkKind>();
 
-        insta::assert_display_snapshot!(ll_line_display, @r###"
+        insta::assert_snapshot!(ll_line_display, @r###"
         Hello  ,     World  !
                      ╰───╯Italic
                      ╰───╯Bold
@@ -273,10 +320,61 @@ mod test {
         ll_line_display.include::<MarkKind>();
         ll_line_display.include::<Link>();
 
-        insta::assert_display_snapshot!(ll_line_display, @r###"
+        insta::assert_snapshot!(ll_line_display, @r###"
         name  @  example.com
         ╰──────────────────╯Italic
         ╰──────────────────╯Link { href: "mailto:name@example.com" }
         "###);
     }
+
+    #[test]
+    fn test_try_create_tokens_errors() {
+        let err = try_create_line_from_input_tokens(
+            vec![
+                InputToken::text(String::from("Hello"), Vec::new()),
+                InputToken::text(String::new(), Vec::new()),
+            ],
+            |text| text.len(),
+        )
+        .err();
+        assert_eq!(err, Some(CreateLineError::EmptyInput { input_idx: 1 }));
+    }
+
+    #[test]
+    fn test_create_tokens_zero_size() {
+        // custom tokens and text measured as taking no position are kept
+        let ll_line = create_line_from_input_tokens(
+            vec![
+                InputToken::text(String::from("a b"), Vec::new()),
+                InputToken::custom(0, Vec::new()),
+            ],
+            |text| text.trim().len(),
+        );
+
+        let ranges: Vec<_> = ll_line
+            .ll_tokens()
+            .iter()
+            .map(|ll_token| (ll_token.pos_starts_at, ll_token.pos_ends_at))
+            .collect();
+        assert_eq!(ranges, vec![(0, 1), (1, 1), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_create_tokens_non_ascii_digit_word() {
+        let ll_line = try_create_line_from_input_tokens(
+            vec![InputToken::text(
+                String::from("1\u{fe0f}\u{20e3} 1٢"),
+                Vec::new(),
+            )],
+            |text| text.chars().count(),
+        )
+        .unwrap();
+
+        insta::assert_snapshot!(LLLineDisplay::new(&ll_line).with::<crate::TextTag>(), @r###"
+        1️⃣     1٢
+        ╰╯SYMB
+            ╰SPACE
+               ╰╯NATN
+        "###);
+    }
 }
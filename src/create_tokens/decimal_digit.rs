@@ -0,0 +1,88 @@
+/// First code point of every run of ten unicode decimal digits (general category `Nd`).
+///
+/// Every run goes from `0` to `9`, so the value of a digit is its distance to the run's zero.
+///
+/// Generated from Unicode 17.0, the version of the Rust standard library's `char` methods.
+const DECIMAL_DIGIT_ZEROS: &[u32] = &[
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6, 0x0B66, 0x0BE6, 0x0C66, 0x0CE6,
+    0x0D66, 0x0DE6, 0x0E50, 0x0ED0, 0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x10D40, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450,
+    0x114D0, 0x11650, 0x116C0, 0x116D0, 0x116DA, 0x11730, 0x118E0, 0x11950, 0x11BF0, 0x11C50,
+    0x11D50, 0x11DA0, 0x11DE0, 0x11F50, 0x16130, 0x16A60, 0x16AC0, 0x16B50, 0x16D70, 0x1CCF0,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E4F0, 0x1E5F1, 0x1E950,
+    0x1FBF0,
+];
+
+/// Numeric value of a unicode decimal digit, such as `7`, `٧` (Arabic-Indic),
+/// `७` (Devanagari) or `７` (full-width).
+pub fn decimal_digit_value(c: char) -> Option<u32> {
+    let code_point = c as u32;
+    let zero = match DECIMAL_DIGIT_ZEROS.binary_search(&code_point) {
+        Ok(idx) => DECIMAL_DIGIT_ZEROS[idx],
+        Err(0) => return None,
+        Err(idx) => DECIMAL_DIGIT_ZEROS[idx - 1],
+    };
+
+    let value = code_point - zero;
+    if value < 10 {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Rewrite the text of a [TextTag::NATN](crate::TextTag::NATN) token with ascii digits,
+/// for example `"١٢٣"` becomes `"123"`.
+///
+/// Returns `None` if `text` contains anything other than unicode decimal digits.
+pub fn to_ascii_digits(text: &str) -> Option<String> {
+    text.chars()
+        .map(|c| decimal_digit_value(c).map(|value| char::from_digit(value, 10).expect("below 10")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_decimal_digit_value() {
+        assert_eq!(super::decimal_digit_value('0'), Some(0));
+        assert_eq!(super::decimal_digit_value('9'), Some(9));
+        assert_eq!(super::decimal_digit_value('٣'), Some(3));
+        assert_eq!(super::decimal_digit_value('۴'), Some(4));
+        assert_eq!(super::decimal_digit_value('७'), Some(7));
+        assert_eq!(super::decimal_digit_value('９'), Some(9));
+        assert_eq!(super::decimal_digit_value('𝟘'), Some(0));
+        // Kawi and Nag Mundari, added in Unicode 15
+        assert_eq!(super::decimal_digit_value('\u{11F55}'), Some(5));
+        assert_eq!(super::decimal_digit_value('\u{1E4F9}'), Some(9));
+        // Ol Onal, added in Unicode 16, starts at U+1E5F1
+        assert_eq!(super::decimal_digit_value('\u{1E5F1}'), Some(0));
+        assert_eq!(super::decimal_digit_value('\u{1E5F0}'), None);
+        // not
+        assert_eq!(super::decimal_digit_value('a'), None);
+        assert_eq!(super::decimal_digit_value('/'), None);
+        assert_eq!(super::decimal_digit_value(':'), None);
+        assert_eq!(super::decimal_digit_value('²'), None);
+        assert_eq!(super::decimal_digit_value('½'), None);
+        assert_eq!(super::decimal_digit_value('Ⅻ'), None);
+    }
+
+    #[test]
+    fn test_decimal_digit_zeros() {
+        for zero in super::DECIMAL_DIGIT_ZEROS {
+            for value in 0..10 {
+                let digit = char::from_u32(zero + value).unwrap();
+                assert!(digit.is_numeric(), "{:?} is not numeric", digit);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_ascii_digits() {
+        assert_eq!(super::to_ascii_digits("0042").as_deref(), Some("0042"));
+        assert_eq!(super::to_ascii_digits("١٢٣").as_deref(), Some("123"));
+        assert_eq!(super::to_ascii_digits("２０２１").as_deref(), Some("2021"));
+        assert_eq!(super::to_ascii_digits("1a"), None);
+    }
+}
@@ -4,8 +4,13 @@
 )]
 
 mod create_tokens;
+mod line_editor;
 mod ll_line;
+mod pipeline;
 mod resolvers;
+mod rich_text;
+mod sentences;
+mod tokenizers;
 mod type_bucket;
 mod type_id_to_many;
 
@@ -13,7 +18,11 @@ mod type_id_to_many;
 mod tests;
 #[allow(deprecated)]
 pub use create_tokens::create_tokens;
-pub use create_tokens::{create_line_from_input_tokens, InputToken};
+pub use create_tokens::{
+    create_line_from_input_tokens, create_line_from_input_tokens_with_tokenizer,
+    decimal_digit_value, to_ascii_digits, try_create_line_from_input_tokens,
+    try_create_line_from_input_tokens_with_tokenizer, CreateLineError, InputToken,
+};
 
 /// Simpler, less featureful version of [create_line_from_input_tokens] which uses utf8 counts as string length ([String::len]).
 ///
@@ -25,14 +34,45 @@ pub fn create_line_from_string<T: AsRef<str>>(input_string: T) -> LLLine {
     create_line_from_input_tokens(vec![token], |s| s.len())
 }
 
+/// Create a line for each sentence of `input_string`, see [SentenceSegmenter].
+///
+/// Use [SentenceResolver] instead to mark the sentences of a single line.
+pub fn create_lines_from_string<T: AsRef<str>>(input_string: T) -> Vec<LLLine> {
+    SentenceSegmenter::default().create_lines(input_string.as_ref())
+}
+
+/// Fallible version of [create_line_from_string], returns an error for empty strings.
+pub fn try_create_line_from_string<T: AsRef<str>>(
+    input_string: T,
+) -> Result<LLLine, CreateLineError> {
+    let token = InputToken::text(input_string.as_ref().to_string(), Vec::new());
+    try_create_line_from_input_tokens(vec![token], |s| s.len())
+}
+
+pub use line_editor::{LineEdit, LineEditError, LineEditor};
+pub use ll_line::resolver_ext;
 pub use ll_line::{
-    x, FinishWith, LLCursorAssignment, LLLine, LLLineDisplay, LLSelection, LToken, Resolver,
-    TextTag,
+    x, FinishWith, LLCursorAssignment, LLLine, LLLineDisplay, LLSelection, LToken, PositionUnit,
+    Resolver, ResolverExt, TextTag, ValueToken,
+};
+pub use pipeline::Pipeline;
+pub use resolvers::{
+    Contraction, ContractionResolver, FuzzyMatch, FuzzyTextMatchAssignResolver, NormalizeResolver,
+    Normalized, Sentence, SentenceResolver, Stopword, StopwordLanguage, StopwordResolver,
+    TextMatchAssignResolver,
+};
+#[cfg(feature = "markdown")]
+pub use rich_text::markdown_to_input_tokens;
+pub use rich_text::{
+    html_to_input_tokens, Heading, HtmlInput, HtmlSourceMap, Image, Link, ListItem, MarkKind,
+};
+pub use sentences::SentenceSegmenter;
+pub use tokenizers::{
+    ChatTokenizer, DefaultTokenizer, RuleTokenizer, Tokenizer, WhitespaceTokenizer,
 };
-pub use resolvers::TextMatchAssignResolver;
 pub use type_bucket::AnyAttribute;
 
 /// Shorthand of [LLLineDisplay::new]
-pub fn debug_line(ll_line: &LLLine) -> LLLineDisplay {
+pub fn debug_line(ll_line: &LLLine) -> LLLineDisplay<'_> {
     LLLineDisplay::new(ll_line)
 }
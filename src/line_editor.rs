@@ -0,0 +1,273 @@
+use crate::create_tokens::tokenize_text;
+use crate::ll_line::{LLToken, LToken, TextTag};
+use crate::tokenizers::{DefaultTokenizer, Tokenizer};
+use crate::{
+    try_create_line_from_input_tokens_with_tokenizer, CreateLineError, InputToken, LLLine, Pipeline,
+};
+
+/// Replace the text from position `start` up to position `end` with `text`.
+///
+/// Positions are measured with the `get_text_size` of the [LineEditor].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+impl LineEdit {
+    pub fn new(start: usize, end: usize, text: impl Into<String>) -> Self {
+        LineEdit {
+            start,
+            end,
+            text: text.into(),
+        }
+    }
+
+    /// Insert `text` at `position`
+    pub fn insert(position: usize, text: impl Into<String>) -> Self {
+        LineEdit::new(position, position, text)
+    }
+
+    /// Remove the text from `start` up to `end`
+    pub fn delete(start: usize, end: usize) -> Self {
+        LineEdit::new(start, end, String::new())
+    }
+}
+
+/// Reasons for [LineEditor::apply] to refuse an edit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineEditError {
+    /// `end` is before `start`, or after the end of the line at `line_end`.
+    OutOfBounds {
+        start: usize,
+        end: usize,
+        line_end: usize,
+    },
+    /// The edit only covers part of the custom token at `token_idx`.
+    SplitsCustomToken { token_idx: usize },
+    /// `position` doesn't fall between two characters of the text of the token at `token_idx`.
+    NotACharBoundary { position: usize, token_idx: usize },
+    /// The edited text couldn't be split into tokens, `input_idx` is always `0`.
+    CreateLine(CreateLineError),
+}
+
+impl std::fmt::Display for LineEditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LineEditError::OutOfBounds {
+                start,
+                end,
+                line_end,
+            } => write!(
+                f,
+                "Cannot edit positions {} to {} of a LLLine ending at {}",
+                start, end, line_end
+            ),
+            LineEditError::SplitsCustomToken { token_idx } => write!(
+                f,
+                "Cannot edit only part of a custom token (token {})",
+                token_idx
+            ),
+            LineEditError::NotACharBoundary {
+                position,
+                token_idx,
+            } => write!(
+                f,
+                "Cannot edit at position {} which is inside a character (token {})",
+                position, token_idx
+            ),
+            LineEditError::CreateLine(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LineEditError {}
+
+impl From<CreateLineError> for LineEditError {
+    fn from(err: CreateLineError) -> Self {
+        LineEditError::CreateLine(err)
+    }
+}
+
+/// Creates lines and keeps them up to date with [LineEdit]s, without tokenizing
+/// the whole line and assigning every attribute again for each edit.
+///
+/// ```
+/// use layered_nlp::{ContractionResolver, InputToken, LineEdit, LineEditor, Pipeline};
+///
+/// let editor = LineEditor::new(|text| text.encode_utf16().count())
+///     .with_pipeline(Pipeline::new().with(ContractionResolver));
+///
+/// let ll_line = editor
+///     .create_line(vec![InputToken::text("I do know".to_string(), Vec::new())])
+///     .unwrap();
+/// let ll_line = editor.apply(ll_line, &LineEdit::insert(4, "n't")).unwrap();
+///
+/// assert_eq!(ll_line.query::<layered_nlp::Contraction>().len(), 1);
+/// ```
+pub struct LineEditor<F, T = DefaultTokenizer> {
+    get_text_size: F,
+    tokenizer: T,
+    pipeline: Pipeline,
+}
+
+impl<F> LineEditor<F>
+where
+    F: Fn(&str) -> usize,
+{
+    /// `get_text_size` measures the positions of the line, see [create_line_from_input_tokens](crate::create_line_from_input_tokens).
+    pub fn new(get_text_size: F) -> Self {
+        LineEditor {
+            get_text_size,
+            tokenizer: DefaultTokenizer::default(),
+            pipeline: Pipeline::new(),
+        }
+    }
+}
+
+impl<F, T> LineEditor<F, T>
+where
+    F: Fn(&str) -> usize,
+    T: Tokenizer,
+{
+    /// Use another [Tokenizer] to split up text
+    pub fn with_tokenizer<U: Tokenizer>(self, tokenizer: U) -> LineEditor<F, U> {
+        LineEditor {
+            get_text_size: self.get_text_size,
+            tokenizer,
+            pipeline: self.pipeline,
+        }
+    }
+
+    /// Resolvers to run on new lines, and on the edited parts of lines
+    pub fn with_pipeline(mut self, pipeline: Pipeline) -> Self {
+        self.pipeline = pipeline;
+        self
+    }
+
+    /// Create a new line and run the pipeline on it
+    pub fn create_line(&self, input: Vec<InputToken>) -> Result<LLLine, CreateLineError> {
+        try_create_line_from_input_tokens_with_tokenizer(
+            input,
+            &self.tokenizer,
+            &self.get_text_size,
+        )
+        .map(|ll_line| self.pipeline.run(ll_line))
+    }
+
+    /// Apply `edit` to `ll_line`.
+    ///
+    /// Only the text around the edit, up to the closest whitespace or custom token on each side,
+    /// is tokenized again, and only the pipeline attributes around it are assigned again.
+    /// Attributes which touched the retokenized text are removed, including custom attributes
+    /// from [InputToken]s. Other attributes move along with their tokens.
+    ///
+    /// The resulting line is expected to be created with the same `get_text_size`, tokenizer and pipeline.
+    pub fn apply(&self, ll_line: LLLine, edit: &LineEdit) -> Result<LLLine, LineEditError> {
+        let ll_tokens = ll_line.ll_tokens();
+        let line_end = ll_tokens.last().map_or(0, |ll_token| ll_token.pos_ends_at);
+        if edit.start > edit.end || edit.end > line_end {
+            return Err(LineEditError::OutOfBounds {
+                start: edit.start,
+                end: edit.end,
+                line_end,
+            });
+        }
+
+        // tokens touching the edit
+        let mut start_idx = ll_tokens.partition_point(|ll_token| ll_token.pos_ends_at < edit.start);
+        let mut end_idx = ll_tokens.partition_point(|ll_token| ll_token.pos_starts_at <= edit.end);
+        // custom tokens which only touch the edit are kept, text tokens are joined with the edit
+        if start_idx < end_idx {
+            let first = &ll_tokens[start_idx];
+            if matches!(first.token, LToken::Value(_)) && first.pos_ends_at == edit.start {
+                start_idx += 1;
+            }
+        }
+        if start_idx < end_idx {
+            let last = &ll_tokens[end_idx - 1];
+            if matches!(last.token, LToken::Value(_)) && last.pos_starts_at == edit.end {
+                end_idx -= 1;
+            }
+        }
+
+        // tokenizers look past the neighbouring tokens, like UAX 29 joining "a.b" into one word,
+        // so the text tokens up to the closest whitespace on each side are tokenized again too
+        let is_space = |ll_token: &LLToken| {
+            matches!(
+                ll_token.token,
+                LToken::Text(_, TextTag::SPACE | TextTag::NEWLINE)
+            )
+        };
+        while start_idx > 0 && matches!(ll_tokens[start_idx - 1].token, LToken::Text(..)) {
+            start_idx -= 1;
+            if is_space(&ll_tokens[start_idx]) {
+                break;
+            }
+        }
+        while end_idx < ll_tokens.len() && matches!(ll_tokens[end_idx].token, LToken::Text(..)) {
+            end_idx += 1;
+            if is_space(&ll_tokens[end_idx - 1]) {
+                break;
+            }
+        }
+
+        let mut before = String::new();
+        let mut after = String::new();
+        for ll_token in &ll_tokens[start_idx..end_idx] {
+            match &ll_token.token {
+                LToken::Text(text, _) => {
+                    let byte_idx = |position: usize| {
+                        self.byte_idx(text, position - ll_token.pos_starts_at)
+                            .ok_or(LineEditError::NotACharBoundary {
+                                position,
+                                token_idx: ll_token.token_idx,
+                            })
+                    };
+
+                    if ll_token.pos_starts_at < edit.start {
+                        before.push_str(&text[..byte_idx(ll_token.pos_ends_at.min(edit.start))?]);
+                    }
+                    if ll_token.pos_ends_at > edit.end {
+                        after.push_str(&text[byte_idx(ll_token.pos_starts_at.max(edit.end))?..]);
+                    }
+                }
+                LToken::Value(_) => {
+                    if ll_token.pos_starts_at < edit.start || ll_token.pos_ends_at > edit.end {
+                        return Err(LineEditError::SplitsCustomToken {
+                            token_idx: ll_token.token_idx,
+                        });
+                    }
+                }
+            }
+        }
+
+        let text = before + &edit.text + &after;
+        let new_tokens = if text.is_empty() {
+            Vec::new()
+        } else {
+            tokenize_text(0, text, &self.tokenizer, &self.get_text_size)?
+        };
+
+        let (ll_line, dirty) = ll_line.splice_tokens(start_idx, end_idx, new_tokens);
+
+        Ok(match dirty {
+            Some(range) => self.pipeline.run_within(ll_line, range),
+            None => ll_line,
+        })
+    }
+
+    /// Find the byte index in `text` which is `size` away from its start
+    fn byte_idx(&self, text: &str, size: usize) -> Option<usize> {
+        if size == 0 {
+            return Some(0);
+        }
+
+        text.char_indices()
+            .map(|(idx, _)| idx)
+            .skip(1)
+            .chain(std::iter::once(text.len()))
+            .find(|idx| (self.get_text_size)(&text[..*idx]) == size)
+    }
+}
@@ -1,26 +1,34 @@
 mod display;
+mod edit;
 mod finish_with;
 mod ll_selection;
+pub mod resolver_ext;
 pub mod x;
 
 pub use finish_with::FinishWith;
 pub use ll_selection::LLSelection;
+pub use resolver_ext::ResolverExt;
 
 use crate::type_bucket::{self, AnyAttribute};
 use crate::type_id_to_many::TypeIdToMany;
 pub use display::LLLineDisplay;
+use std::any::{Any, TypeId};
 use std::fmt::{self, Write};
 use std::iter::FromIterator;
 use std::{collections::HashMap, rc::Rc};
-pub use x::{Attr, AttrEq};
 use x::{XForwards, XMatch};
 
 /// [TextTag] is an attribute added at the beginning of every new line.
 ///
 /// Each piece of a line is sort of "tokenized" and each token is assigned a [TextTag] attribute.
+///
+/// More tags may be added by new [Tokenizer](crate::Tokenizer)s, so matches on it need a wildcard arm.
 #[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum TextTag {
-    /// Natural number like `0`, `1200`, `0004`
+    /// Natural number like `0`, `1200`, `0004`, or made of other unicode decimal digits like `١٢٣`.
+    ///
+    /// See [to_ascii_digits](crate::to_ascii_digits) to get its numeric value.
     NATN,
     /// English sentence punctuation symbols.
     /// `,`, `.`, `!`, `;`, `:`, `?`, `'`, `"`
@@ -33,13 +41,144 @@ pub enum TextTag {
     ///
     /// For example: `yello`, `Paris`, `don't`, `should've`
     WORD,
+    /// A line break like `\n` or `\r\n`, only from the [ChatTokenizer](crate::ChatTokenizer)
+    NEWLINE,
+    /// An emoji, including sequences like `👍🏽`, `👨‍👩‍👧` or `🇫🇷`, only from the [ChatTokenizer](crate::ChatTokenizer)
+    EMOJI,
+    /// A link like `https://example.com/path?query`, only from the [ChatTokenizer](crate::ChatTokenizer)
+    URL,
+    /// An email address like `name@example.com`, only from the [ChatTokenizer](crate::ChatTokenizer)
+    EMAIL,
+    /// A mention like `@someone`, only from the [ChatTokenizer](crate::ChatTokenizer)
+    MENTION,
+    /// A hashtag or channel like `#general`, only from the [ChatTokenizer](crate::ChatTokenizer)
+    HASHTAG,
 }
 
 #[derive(Debug)]
 pub enum LToken {
     Text(String, TextTag),
-    /// TODO: something more interesting
-    Value,
+    /// An atomic token created from an [InputToken::Custom](crate::InputToken::Custom),
+    /// like a mention, a date or a variable embedded in the text.
+    Value(ValueToken),
+}
+
+/// Label and typed payload of a [LToken::Value].
+///
+/// Match payloads with [x::value].
+#[derive(Default)]
+pub struct ValueToken {
+    label: String,
+    payload: Option<Box<dyn ValuePayload>>,
+}
+
+trait ValuePayload: Any + fmt::Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any + fmt::Debug + Send + Sync> ValuePayload for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl ValueToken {
+    /// Value without payload, `label` is shown by [LLLineDisplay].
+    pub fn new(label: impl Into<String>) -> Self {
+        ValueToken {
+            label: label.into(),
+            payload: None,
+        }
+    }
+
+    pub fn with_payload<T: 'static + fmt::Debug + Send + Sync>(mut self, payload: T) -> Self {
+        self.payload = Some(Box::new(payload));
+        self
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Returns the payload if it is a `T`
+    pub fn payload<T: 'static>(&self) -> Option<&T> {
+        self.payload
+            .as_ref()
+            // deref the box, which is a ValuePayload itself
+            .and_then(|payload| (**payload).as_any().downcast_ref())
+    }
+}
+
+impl fmt::Debug for ValueToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValueToken")
+            .field("label", &self.label)
+            .field("payload", &self.payload)
+            .finish()
+    }
+}
+
+/// The unit in which positions of a [LLLine] are measured.
+///
+/// Every [LLToken] records its position in all of these units at once.
+///
+/// Except for [PositionUnit::Custom], these count the text of the tokens, so custom tokens
+/// take 0 positions in them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PositionUnit {
+    /// The size returned by `get_text_size` (or the custom size) when the line was created
+    Custom,
+    /// UTF-8 bytes, like [str::len]
+    Byte,
+    /// Unicode scalar values, like `str::chars().count()`
+    Char,
+    /// UTF-16 code units, like `String.length` in JavaScript
+    Utf16,
+    /// Extended grapheme clusters, counted token by token: a cluster split across two tokens,
+    /// like a combining mark in a token of its own, counts once in each.
+    Grapheme,
+}
+
+/// Offsets into the text of a line, custom tokens don't take up any text.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct TextPositions {
+    pub(crate) byte: usize,
+    pub(crate) char: usize,
+    pub(crate) utf16: usize,
+    pub(crate) grapheme: usize,
+}
+
+impl TextPositions {
+    /// The size of `text` in each unit
+    pub(crate) fn of(text: &str) -> Self {
+        use unicode_segmentation::UnicodeSegmentation;
+
+        TextPositions {
+            byte: text.len(),
+            char: text.chars().count(),
+            utf16: text.encode_utf16().count(),
+            grapheme: text.graphemes(true).count(),
+        }
+    }
+
+    pub(crate) fn add(self, other: TextPositions) -> Self {
+        TextPositions {
+            byte: self.byte + other.byte,
+            char: self.char + other.char,
+            utf16: self.utf16 + other.utf16,
+            grapheme: self.grapheme + other.grapheme,
+        }
+    }
+
+    /// Move positions following `from` to follow `to` instead
+    fn shift(self, from: TextPositions, to: TextPositions) -> Self {
+        TextPositions {
+            byte: self.byte - from.byte + to.byte,
+            char: self.char - from.char + to.char,
+            utf16: self.utf16 - from.utf16 + to.utf16,
+            grapheme: self.grapheme - from.grapheme + to.grapheme,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -50,6 +189,10 @@ pub struct LLToken {
     pub(crate) pos_starts_at: usize,
     // token span position (not token index)
     pub(crate) pos_ends_at: usize,
+    // token span position in the other units
+    pub(crate) text_starts_at: TextPositions,
+    // token span position in the other units
+    pub(crate) text_ends_at: TextPositions,
     pub(crate) token: LToken,
 }
 
@@ -58,10 +201,30 @@ impl LLToken {
     pub fn get_token(&self) -> &LToken {
         &self.token
     }
+
+    /// Position at which this token starts, measured in `unit`.
+    pub fn starts_at(&self, unit: PositionUnit) -> usize {
+        Self::position_in(self.pos_starts_at, &self.text_starts_at, unit)
+    }
+
+    /// Position at which this token ends, measured in `unit`.
+    pub fn ends_at(&self, unit: PositionUnit) -> usize {
+        Self::position_in(self.pos_ends_at, &self.text_ends_at, unit)
+    }
+
+    fn position_in(custom: usize, text: &TextPositions, unit: PositionUnit) -> usize {
+        match unit {
+            PositionUnit::Custom => custom,
+            PositionUnit::Byte => text.byte,
+            PositionUnit::Char => text.char,
+            PositionUnit::Utf16 => text.utf16,
+            PositionUnit::Grapheme => text.grapheme,
+        }
+    }
 }
 
 /// (starts at, ends at) token indexes
-type LRange = (usize, usize);
+pub(crate) type LRange = (usize, usize);
 /// (starts at, ends at) token positions
 type PositionRange = (usize, usize);
 
@@ -74,28 +237,37 @@ struct LLLineAttrs {
     /// match_backwards uses [LLSelection::start_idx]
     ends_at: Vec<TypeIdToMany<LRange>>,
     values: HashMap<LRange, type_bucket::TypeBucket>,
+    /// Most tokens after its start taken by an attribute of each type, for [LLLine::attrs_covering_position]
+    longest: HashMap<TypeId, usize>,
 }
 
 pub struct LLLineFind<'l, Found> {
-    start_pos_at: usize,
-    end_pos_at: usize,
+    ll_line: &'l LLLine,
+    start_idx: usize,
+    end_idx: usize,
     found: Found,
-    _phantom: std::marker::PhantomData<&'l ()>,
 }
 
 impl<'l, Found: fmt::Debug> fmt::Debug for LLLineFind<'l, Found> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("LLLineFind")
-            .field("start", &self.start_pos_at)
-            .field("end", &self.end_pos_at)
+            .field("start", &self.ll_line.pos_start_at(self.start_idx))
+            .field("end", &self.ll_line.pos_end_at(self.end_idx))
             .field("found", &self.found)
             .finish()
     }
 }
 
 impl<'l, Found> LLLineFind<'l, Found> {
+    /// Positions measured with the `get_text_size` used to create the line,
+    /// same as `range_in(PositionUnit::Custom)`.
     pub fn range(&self) -> PositionRange {
-        (self.start_pos_at, self.end_pos_at)
+        self.range_in(PositionUnit::Custom)
+    }
+    /// Positions measured in the given unit
+    pub fn range_in(&self, unit: PositionUnit) -> PositionRange {
+        self.ll_line
+            .position_range((self.start_idx, self.end_idx), unit)
     }
     pub fn attr(&self) -> &Found {
         &self.found
@@ -111,33 +283,10 @@ pub struct LLLine {
 
 impl LLLine {
     pub(crate) fn new(ll_tokens: Vec<LLToken>) -> Self {
-        let starts_at: Vec<TypeIdToMany<LRange>> =
-            (0..ll_tokens.len()).map(|_| Default::default()).collect();
-        let ends_at: Vec<TypeIdToMany<LRange>> =
-            (0..ll_tokens.len()).map(|_| Default::default()).collect();
-
-        let mut attrs = LLLineAttrs {
-            ranges: Default::default(),
-            starts_at,
-            ends_at,
-            values: Default::default(),
-        };
+        let mut attrs = LLLineAttrs::new(ll_tokens.len());
 
         for (token_idx, ll_token) in ll_tokens.iter().enumerate() {
-            match &ll_token.token {
-                LToken::Text(text, tag) => {
-                    let mut chars = text.chars();
-                    if let (Some(c), None) = (chars.next(), chars.next()) {
-                        // insert char automatically if just one char
-                        attrs.insert((token_idx, token_idx), c);
-                    }
-                    // insert TextTag automatically
-                    attrs.insert((token_idx, token_idx), tag.clone());
-                }
-                LToken::Value => {
-                    // nothing to do...
-                }
-            }
+            attrs.insert_token_attrs(token_idx, &ll_token.token);
         }
 
         LLLine { ll_tokens, attrs }
@@ -176,6 +325,30 @@ impl LLLine {
 
         self
     }
+
+    /// Like [LLLine::run], but for type erased assignments, and only selecting the tokens in `range`.
+    pub(crate) fn run_any_within<G>(mut self, range: LRange, go: G) -> Self
+    where
+        G: FnOnce(LLSelection) -> Vec<(LRange, AnyAttribute)>,
+    {
+        let ll_line = Rc::new(self);
+
+        let assignments = go(LLSelection {
+            ll_line: ll_line.clone(),
+            start_idx: range.0,
+            end_idx: range.1,
+        });
+
+        self = Rc::try_unwrap(ll_line)
+            .map_err(drop)
+            .expect("there is no other Rc currently");
+
+        for ((start_idx, end_idx), attr) in assignments {
+            self.add_any_attrs(start_idx, end_idx, vec![attr]);
+        }
+
+        self
+    }
     pub(crate) fn add_any_attrs(
         &mut self,
         start_idx: usize,
@@ -185,17 +358,7 @@ impl LLLine {
         let range = (start_idx, end_idx);
 
         for attr in attrs {
-            self.attrs
-                .starts_at
-                .get_mut(start_idx)
-                .expect("has initial starts_at value in bounds")
-                .insert_any_distinct(attr.type_id(), range);
-            self.attrs
-                .ends_at
-                .get_mut(end_idx)
-                .expect("has initial ends_at value in bounds")
-                .insert_any_distinct(attr.type_id(), range);
-            self.attrs.ranges.insert_any_distinct(attr.type_id(), range);
+            self.attrs.insert_range(attr.type_id(), range);
             self.attrs
                 .values
                 .entry(range)
@@ -214,16 +377,21 @@ impl LLLine {
     pub fn find<'l, M: XMatch<'l>>(&'l self, matcher: &M) -> Vec<LLLineFind<'l, M::Out>> {
         (0..self.ll_tokens.len())
             .flat_map(|i| {
-                let forwards = XForwards { from_idx: i };
+                let forwards = XForwards {
+                    from_idx: i,
+                    selection: (0, self.ll_tokens.len() - 1),
+                };
 
                 matcher
                     .go(&forwards, self)
                     .into_iter()
+                    // zero-width matches don't select any token
+                    .filter(move |(_, next_idx)| (i..self.ll_tokens.len()).contains(&next_idx.0))
                     .map(move |(out, next_idx)| LLLineFind {
-                        start_pos_at: self.pos_start_at(i),
-                        end_pos_at: self.pos_end_at(next_idx.0),
+                        ll_line: self,
+                        start_idx: i,
+                        end_idx: next_idx.0,
                         found: out,
-                        _phantom: std::marker::PhantomData,
                     })
             })
             .collect()
@@ -244,8 +412,111 @@ impl LLLine {
             .pos_starts_at
     }
 
+    /// Go from (starts at, ends at) token _indexes_ to their positions measured in `unit`.
+    pub fn position_range(&self, range: LRange, unit: PositionUnit) -> PositionRange {
+        (
+            self.ll_tokens
+                .get(range.0)
+                .expect("position_range start in bounds")
+                .starts_at(unit),
+            self.ll_tokens
+                .get(range.1)
+                .expect("position_range end in bounds")
+                .ends_at(unit),
+        )
+    }
+
+    /// Same as [LLLine::query] but with ranges of positions measured in `unit`, instead of token indexes.
+    pub fn query_in<T: 'static>(
+        &self,
+        unit: PositionUnit,
+    ) -> Vec<(PositionRange, String, Vec<&T>)> {
+        self.query::<T>()
+            .into_iter()
+            .map(|(range, text, values)| (self.position_range(range, unit), text, values))
+            .collect()
+    }
+
+    /// Find the token spanning `pos`, using the positions from `get_text_size`.
+    ///
+    /// A token spans from its start position up to, but not including, its end position.
+    pub fn token_at_position(&self, pos: usize) -> Option<&LLToken> {
+        self.token_idx_at_position(pos)
+            .map(|token_idx| &self.ll_tokens[token_idx])
+    }
+
+    /// Binary search for the index of the token spanning `pos`
+    fn token_idx_at_position(&self, pos: usize) -> Option<usize> {
+        let token_idx = self
+            .ll_tokens
+            .partition_point(|ll_token| ll_token.pos_ends_at <= pos);
+
+        self.ll_tokens
+            .get(token_idx)
+            .filter(|ll_token| ll_token.pos_starts_at <= pos)
+            .map(|_| token_idx)
+    }
+
+    /// Returns the `T` attributes assigned to a range of tokens which includes the token at `pos`.
+    ///
+    /// Useful for hover cards and other cursor-aware features in editors. Only the tokens
+    /// which the longest `T` attribute could start from are looked at, in line order.
+    pub fn attrs_covering_position<T: 'static>(&self, pos: usize) -> Vec<LLLineFind<'_, &T>> {
+        let (token_idx, longest) = match (
+            self.token_idx_at_position(pos),
+            self.attrs.longest.get(&TypeId::of::<T>()),
+        ) {
+            (Some(token_idx), Some(longest)) => (token_idx, *longest),
+            _ => return Vec::new(),
+        };
+
+        self.attrs.starts_at[token_idx.saturating_sub(longest)..=token_idx]
+            .iter()
+            .flat_map(|starts_at| starts_at.get::<T>())
+            .filter(|(_, end_idx)| token_idx <= *end_idx)
+            .flat_map(|range| {
+                self.attrs.values[range]
+                    .get::<T>()
+                    .iter()
+                    .map(move |value| LLLineFind {
+                        ll_line: self,
+                        start_idx: range.0,
+                        end_idx: range.1,
+                        found: value,
+                    })
+            })
+            .collect()
+    }
+
+    /// Range of the tokens overlapping the positions from `start` up to `end`, see
+    /// [LLSelection::from_position_range].
+    pub(crate) fn token_range_for_position_range(
+        &self,
+        start: usize,
+        end: usize,
+    ) -> Option<LRange> {
+        if start == end {
+            let token_idx = self.token_idx_at_position(start)?;
+            return Some((token_idx, token_idx));
+        }
+
+        // first token ending after start, and last token starting before end
+        let start_idx = self
+            .ll_tokens
+            .partition_point(|ll_token| ll_token.pos_ends_at <= start);
+        let end_idx = self
+            .ll_tokens
+            .partition_point(|ll_token| ll_token.pos_starts_at < end)
+            .checked_sub(1)?;
+
+        if start_idx > end_idx {
+            return None;
+        }
+
+        Some((start_idx, end_idx))
+    }
     /// Returns Attributes' information outside `LLLine`
-    pub fn query<'a, T: 'static>(&'a self) -> Vec<(LRange, String, Vec<&T>)> {
+    pub fn query<T: 'static>(&self) -> Vec<(LRange, String, Vec<&T>)> {
         self.attrs
             .ranges
             .get::<T>()
@@ -255,7 +526,7 @@ impl LLLine {
                     String::from_iter(self.ll_tokens[range.0..=range.1].iter().map(|token| {
                         match &token.token {
                             LToken::Text(text, _) => text,
-                            LToken::Value => "",
+                            LToken::Value(_) => "",
                         }
                     }));
 
@@ -270,16 +541,51 @@ impl LLLine {
 }
 
 impl LLLineAttrs {
-    fn insert<T: 'static + std::fmt::Debug + Send + Sync>(&mut self, range: LRange, value: T) {
+    fn new(token_count: usize) -> Self {
+        LLLineAttrs {
+            ranges: Default::default(),
+            starts_at: (0..token_count).map(|_| Default::default()).collect(),
+            ends_at: (0..token_count).map(|_| Default::default()).collect(),
+            values: Default::default(),
+            longest: Default::default(),
+        }
+    }
+
+    /// Index the `range` of an attribute of type `type_id`
+    fn insert_range(&mut self, type_id: TypeId, range: LRange) {
         self.starts_at
             .get_mut(range.0)
             .expect("has initial starts_at value in bounds")
-            .insert_distinct::<T>(range);
+            .insert_any_distinct(type_id, range);
         self.ends_at
             .get_mut(range.1)
             .expect("has initial ends_at value in bounds")
-            .insert_distinct::<T>(range);
-        self.ranges.insert_distinct::<T>(range);
+            .insert_any_distinct(type_id, range);
+        self.ranges.insert_any_distinct(type_id, range);
+        let longest = self.longest.entry(type_id).or_default();
+        *longest = (*longest).max(range.1 - range.0);
+    }
+
+    /// Insert the attributes every token gets automatically
+    fn insert_token_attrs(&mut self, token_idx: usize, token: &LToken) {
+        match token {
+            LToken::Text(text, tag) => {
+                let mut chars = text.chars();
+                if let (Some(c), None) = (chars.next(), chars.next()) {
+                    // insert char automatically if just one char
+                    self.insert((token_idx, token_idx), c);
+                }
+                // insert TextTag automatically
+                self.insert((token_idx, token_idx), tag.clone());
+            }
+            LToken::Value(_) => {
+                // nothing to do...
+            }
+        }
+    }
+
+    fn insert<T: 'static + std::fmt::Debug + Send + Sync>(&mut self, range: LRange, value: T) {
+        self.insert_range(TypeId::of::<T>(), range);
         self.values.entry(range).or_default().insert(value);
     }
 }
@@ -301,6 +607,15 @@ pub struct LLCursorAssignment<Attr> {
     value: Attr,
 }
 
+impl<Attr: std::fmt::Debug + 'static + Send + Sync> LLCursorAssignment<Attr> {
+    pub(crate) fn into_any(self) -> (LRange, AnyAttribute) {
+        (
+            (self.start_idx, self.end_idx),
+            AnyAttribute::new(self.value),
+        )
+    }
+}
+
 pub trait Resolver {
     /// The kind of value that this resolver will assign into the LLLine.
     ///
@@ -43,17 +43,23 @@ impl<'a> std::fmt::Display for LLLineDisplay<'a> {
                 if is_first {
                     is_first = false;
                 } else {
-                    opening_line.extend(std::iter::repeat(' ').take(SPACE_PADDING));
+                    opening_line.extend(std::iter::repeat_n(' ', SPACE_PADDING));
                 }
 
                 token_idx_to_start_display_char_idx.push(UnicodeWidthStr::width(&*opening_line));
 
                 match &ll_token.token {
+                    LToken::Text(text, TextTag::SPACE | TextTag::NEWLINE)
+                        if text.contains(['\n', '\r']) =>
+                    {
+                        // keep the display on one line
+                        write!(&mut opening_line, "{}", text.escape_debug())?;
+                    }
                     LToken::Text(text, _) => {
                         opening_line.push_str(text);
                     }
-                    LToken::Value { .. } => {
-                        write!(&mut opening_line, "<>")?;
+                    LToken::Value(value) => {
+                        write!(&mut opening_line, "<{}>", value.label())?;
                     }
                 }
 
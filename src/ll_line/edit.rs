@@ -0,0 +1,176 @@
+use std::any::TypeId;
+use std::collections::HashSet;
+
+use super::{LLLine, LLLineAttrs, LLToken, LRange, LToken, TextPositions};
+
+impl LLLine {
+    /// Replace the tokens from `start_idx` up to (excluding) `end_idx` with `new_tokens`, given with their custom size.
+    ///
+    /// Attributes of the replaced tokens and attributes spanning across them are dropped.
+    /// Other attributes are moved along with their tokens.
+    ///
+    /// Returns the new line, and the range of tokens which need to be resolved again.
+    pub(crate) fn splice_tokens(
+        self,
+        start_idx: usize,
+        end_idx: usize,
+        new_tokens: Vec<(LToken, usize)>,
+    ) -> (LLLine, Option<LRange>) {
+        let LLLine {
+            ll_tokens,
+            attrs: mut old_attrs,
+        } = self;
+
+        let old_len = end_idx - start_idx;
+        let new_len = new_tokens.len();
+        let token_count = ll_tokens.len() - old_len + new_len;
+        // new index of a token which wasn't replaced
+        let move_idx = |idx: usize| {
+            if idx < start_idx {
+                idx
+            } else {
+                idx - old_len + new_len
+            }
+        };
+
+        let (mut pos, mut text_pos) = match start_idx.checked_sub(1) {
+            Some(prev_idx) => (
+                ll_tokens[prev_idx].pos_ends_at,
+                ll_tokens[prev_idx].text_ends_at,
+            ),
+            None => (0, TextPositions::default()),
+        };
+        let (old_pos, old_text_pos) = match end_idx.checked_sub(1) {
+            Some(prev_idx) => (
+                ll_tokens[prev_idx].pos_ends_at,
+                ll_tokens[prev_idx].text_ends_at,
+            ),
+            None => (0, TextPositions::default()),
+        };
+
+        let mut old_tokens = ll_tokens.into_iter();
+        let mut ll_tokens: Vec<LLToken> = old_tokens.by_ref().take(start_idx).collect();
+        for (token, size) in new_tokens {
+            let next_text_pos = match &token {
+                LToken::Text(text, _) => text_pos.add(TextPositions::of(text)),
+                LToken::Value(_) => text_pos,
+            };
+            ll_tokens.push(LLToken {
+                token_idx: ll_tokens.len(),
+                pos_starts_at: pos,
+                pos_ends_at: pos + size,
+                text_starts_at: text_pos,
+                text_ends_at: next_text_pos,
+                token,
+            });
+            pos += size;
+            text_pos = next_text_pos;
+        }
+        for ll_token in old_tokens.skip(old_len) {
+            ll_tokens.push(LLToken {
+                token_idx: ll_tokens.len(),
+                pos_starts_at: ll_token.pos_starts_at - old_pos + pos,
+                pos_ends_at: ll_token.pos_ends_at - old_pos + pos,
+                text_starts_at: ll_token.text_starts_at.shift(old_text_pos, text_pos),
+                text_ends_at: ll_token.text_ends_at.shift(old_text_pos, text_pos),
+                token: ll_token.token,
+            });
+        }
+
+        // (start, end exclusive) of the tokens to resolve again, in new token indexes,
+        // including the neighbours of the edit which may now match differently
+        let mut dirty = (
+            start_idx.saturating_sub(1),
+            (start_idx + new_len + 1).min(token_count),
+        );
+
+        let all_ranges: Vec<(TypeId, LRange)> = old_attrs
+            .ranges
+            .iter()
+            .flat_map(|(type_id, ranges)| ranges.iter().map(move |range| (type_id, *range)))
+            .collect();
+        let mut dropped: HashSet<(TypeId, LRange)> = HashSet::new();
+
+        for (type_id, (start, end)) in &all_ranges {
+            if *start < end_idx && *end >= start_idx {
+                // touches the edit
+                dropped.insert((*type_id, (*start, *end)));
+                dirty.0 = dirty.0.min(*start);
+                dirty.1 = dirty.1.max(if *end >= end_idx {
+                    move_idx(*end) + 1
+                } else {
+                    start_idx + new_len
+                });
+            }
+        }
+
+        let mut attrs = LLLineAttrs::new(token_count);
+        for (type_id, range) in all_ranges {
+            if dropped.contains(&(type_id, range)) {
+                continue;
+            }
+
+            let values = old_attrs
+                .values
+                .get_mut(&range)
+                .expect("values for range")
+                .remove_type(type_id);
+            attrs.insert_bucket(type_id, (move_idx(range.0), move_idx(range.1)), values);
+        }
+        for (token_idx, ll_token) in ll_tokens.iter().enumerate().skip(start_idx).take(new_len) {
+            attrs.insert_token_attrs(token_idx, &ll_token.token);
+        }
+
+        let dirty = if dirty.0 < dirty.1 {
+            Some((dirty.0, dirty.1 - 1))
+        } else {
+            None
+        };
+
+        (LLLine { ll_tokens, attrs }, dirty)
+    }
+
+    /// Remove the attributes of `type_ids` overlapping the tokens of `range`.
+    ///
+    /// Returns the ranges of the removed attributes.
+    pub(crate) fn remove_attrs_overlapping(
+        &mut self,
+        type_ids: &[TypeId],
+        range: LRange,
+    ) -> Vec<LRange> {
+        let mut removed = Vec::new();
+        for type_id in type_ids {
+            let overlapping: Vec<LRange> = self
+                .attrs
+                .ranges
+                .get_any(*type_id)
+                .iter()
+                .filter(|(start, end)| *start <= range.1 && *end >= range.0)
+                .copied()
+                .collect();
+
+            for attr_range in overlapping {
+                self.attrs.ranges.remove_any(*type_id, &attr_range);
+                self.attrs.starts_at[attr_range.0].remove_any(*type_id, &attr_range);
+                self.attrs.ends_at[attr_range.1].remove_any(*type_id, &attr_range);
+                if let Some(values) = self.attrs.values.get_mut(&attr_range) {
+                    values.remove_type(*type_id);
+                }
+                removed.push(attr_range);
+            }
+        }
+        removed
+    }
+}
+
+impl LLLineAttrs {
+    fn insert_bucket(
+        &mut self,
+        type_id: TypeId,
+        range: LRange,
+        values: crate::type_bucket::TypeBucket,
+    ) {
+        self.insert_range(type_id, range);
+        self.values.entry(range).or_default().append(values);
+    }
+}
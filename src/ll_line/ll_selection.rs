@@ -1,5 +1,5 @@
-use super::x::{XBackwards, XForwards};
-use super::{assert_ll_lines_equals, LLCursorAssignment, LLLine, Rc, XMatch};
+use super::x::{Captures, CollectCaptures, XBackwards, XContains, XForwards};
+use super::{assert_ll_lines_equals, LLCursorAssignment, LLLine, LLToken, Rc, XMatch};
 
 // # List of operations
 //
@@ -65,6 +65,25 @@ impl LLSelection {
         }
     }
 
+    /// Select the tokens overlapping the positions from `start` up to `end`, taking the line
+    /// as returned by [LLLine::run] or shared in an [Rc].
+    ///
+    /// An empty range (`start == end`) selects the token at `start`, like a cursor would.
+    /// Returns `None` if no token overlaps the range.
+    pub fn from_position_range(
+        ll_line: impl Into<Rc<LLLine>>,
+        start: usize,
+        end: usize,
+    ) -> Option<Self> {
+        let ll_line = ll_line.into();
+        let (start_idx, end_idx) = ll_line.token_range_for_position_range(start, end)?;
+        Some(LLSelection {
+            ll_line,
+            start_idx,
+            end_idx,
+        })
+    }
+
     pub fn split_by<'a, M: XMatch<'a>>(&'a self, matcher: &M) -> Vec<LLSelection> {
         let matches = self.find_by(matcher);
 
@@ -92,7 +111,7 @@ impl LLSelection {
         let end_opt = self.selection_from(matches.last().unwrap().0.end_idx + 1, self.end_idx);
         start_opt
             .into_iter()
-            .chain(matches.windows(2).into_iter().filter_map(|m| {
+            .chain(matches.windows(2).filter_map(|m| {
                 m[1].0
                     .start_idx
                     .checked_sub(1)
@@ -103,13 +122,19 @@ impl LLSelection {
     }
 
     pub fn find_by<'a, M: XMatch<'a>>(&'a self, matcher: &M) -> Vec<(LLSelection, M::Out)> {
+        let line_len = self.ll_line.ll_tokens.len();
         (self.start_idx..=self.end_idx)
-            .map(|i| {
-                let forwards = XForwards { from_idx: i };
+            .flat_map(|i| {
+                let forwards = XForwards {
+                    from_idx: i,
+                    selection: (self.start_idx, self.end_idx),
+                };
 
                 matcher
                     .go(&forwards, &self.ll_line)
                     .into_iter()
+                    // zero-width matches don't select any token
+                    .filter(move |(_, next_idx)| (i..line_len).contains(&next_idx.0))
                     .map(move |(out, next_idx)| {
                         (
                             LLSelection {
@@ -121,7 +146,81 @@ impl LLSelection {
                         )
                     })
             })
-            .flatten()
+            .collect()
+    }
+
+    /// Like [LLSelection::find_by], with the [Captures] of each match instead of the matcher output
+    ///
+    /// ```
+    /// use layered_nlp::{create_line_from_string, x, LLSelection, TextMatchAssignResolver};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Amount(u32);
+    ///
+    /// let ll_line = create_line_from_string("pay $1000 now")
+    ///     .run(&TextMatchAssignResolver::new_case_insensitive_str_arr([("1000", Amount(1000))]));
+    /// let selection = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+    ///
+    /// let found = selection.find_captures(&x::seq((
+    ///     x::capture("symbol", x::token_text()),
+    ///     x::capture("amount", x::attr::<Amount>()),
+    /// )));
+    /// assert_eq!(found.len(), 1);
+    ///
+    /// let (_, captures) = &found[0];
+    /// assert_eq!(captures.text("symbol"), Some("$"));
+    /// assert_eq!(captures.get::<Amount>("amount"), Some(&Amount(1000)));
+    /// assert!(captures.selection("amount").is_some());
+    /// ```
+    pub fn find_captures<'a, M>(&'a self, matcher: &M) -> Vec<(LLSelection, Captures<'a>)>
+    where
+        M: XMatch<'a>,
+        M::Out: CollectCaptures<'a>,
+    {
+        self.find_by(matcher)
+            .into_iter()
+            .map(|(selection, out)| {
+                let mut captures = Captures::new(self.ll_line.clone());
+                out.collect_captures(&mut captures);
+                (selection, captures)
+            })
+            .collect()
+    }
+
+    /// Find matches anywhere in the selection, in any order and without taking the same tokens twice.
+    ///
+    /// Returns every combination found, each with the full selection. Use [x::multiple](crate::x::multiple)
+    /// to match several of the same attribute.
+    ///
+    /// ```
+    /// use layered_nlp::{create_line_from_string, x, LLSelection, TextMatchAssignResolver};
+    ///
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Person(&'static str);
+    /// #[derive(Debug, Clone, PartialEq)]
+    /// struct Amount(u32);
+    ///
+    /// let ll_line = create_line_from_string("pay 1000 from Alice to Bob")
+    ///     .run(&TextMatchAssignResolver::new_case_insensitive_str_arr([
+    ///         ("Alice", Person("Alice")),
+    ///         ("Bob", Person("Bob")),
+    ///     ]))
+    ///     .run(&TextMatchAssignResolver::new_case_insensitive_str_arr([("1000", Amount(1000))]));
+    /// let clause = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+    ///
+    /// let found = clause.contains_in_any_order(&(x::multiple::<Person, 2>(), x::attr::<Amount>()));
+    /// assert_eq!(found.len(), 1);
+    /// assert_eq!(found[0].0, clause);
+    /// assert_eq!(found[0].1, ([&Person("Alice"), &Person("Bob")], &Amount(1000)));
+    /// ```
+    pub fn contains_in_any_order<'a, C: XContains<'a>>(
+        &'a self,
+        matchers: &C,
+    ) -> Vec<(LLSelection, C::Out)> {
+        matchers
+            .contained((self.start_idx, self.end_idx), &self.ll_line)
+            .into_iter()
+            .map(|(out, _)| (self.clone(), out))
             .collect()
     }
 
@@ -136,13 +235,19 @@ impl LLSelection {
         &'a self,
         matcher: &M,
     ) -> Vec<(LLSelection, M::Out)> {
+        let line_len = self.ll_line.ll_tokens.len();
         (self.start_idx..=self.end_idx)
-            .map(|i| {
-                let forwards = XForwards { from_idx: i };
+            .flat_map(|i| {
+                let forwards = XForwards {
+                    from_idx: i,
+                    selection: (self.start_idx, self.end_idx),
+                };
 
                 matcher
                     .go(&forwards, &self.ll_line)
                     .into_iter()
+                    // zero-width matches don't select any token
+                    .filter(move |(_, next_idx)| (i..line_len).contains(&next_idx.0))
                     .map(move |(out, next_idx)| {
                         (
                             LLSelection {
@@ -154,10 +259,16 @@ impl LLSelection {
                         )
                     })
                     .chain({
-                        let backwards = XBackwards { from_idx: i };
-
-                        matcher.go(&backwards, &self.ll_line).into_iter().map(
-                            move |(out, next_idx)| {
+                        let backwards = XBackwards {
+                            from_idx: i,
+                            selection: (self.start_idx, self.end_idx),
+                        };
+
+                        matcher
+                            .go(&backwards, &self.ll_line)
+                            .into_iter()
+                            .filter(move |(_, next_idx)| next_idx.0 <= i)
+                            .map(move |(out, next_idx)| {
                                 (
                                     LLSelection {
                                         start_idx: next_idx.0,
@@ -166,11 +277,9 @@ impl LLSelection {
                                     },
                                     out,
                                 )
-                            },
-                        )
+                            })
                     })
             })
-            .flatten()
             .collect()
     }
 
@@ -183,6 +292,7 @@ impl LLSelection {
 
         let forwards = XForwards {
             from_idx: self.end_idx + 1,
+            selection: (self.start_idx, self.end_idx),
         };
 
         matcher
@@ -229,6 +339,7 @@ impl LLSelection {
 
         let backwards = XBackwards {
             from_idx: self.start_idx - 1,
+            selection: (self.start_idx, self.end_idx),
         };
 
         matcher
@@ -268,7 +379,17 @@ impl LLSelection {
         }
     }
 
-    fn selection_from(&self, mut start_idx: usize, mut end_idx: usize) -> Option<LLSelection> {
+    /// The selected tokens
+    pub(crate) fn ll_tokens(&self) -> &[LLToken] {
+        &self.ll_line.ll_tokens[self.start_idx..=self.end_idx]
+    }
+
+    /// Selection of the line's tokens from `start_idx` up to `end_idx` (inclusive) within this selection
+    pub(crate) fn selection_from(
+        &self,
+        mut start_idx: usize,
+        mut end_idx: usize,
+    ) -> Option<LLSelection> {
         start_idx = start_idx.max(self.start_idx);
         end_idx = end_idx.min(self.end_idx);
         if start_idx <= end_idx {
@@ -320,12 +441,13 @@ impl LLSelection {
                 .go(
                     &XForwards {
                         from_idx: self.start_idx,
+                        selection: (self.start_idx, self.end_idx),
                     },
                     &self.ll_line,
                 )
                 .first()
             {
-                new_start = (first_match.1).0 + 1;
+                new_start = (first_match.1).0.wrapping_add(1);
             }
         }
 
@@ -334,6 +456,7 @@ impl LLSelection {
                 .go(
                     &XBackwards {
                         from_idx: self.end_idx,
+                        selection: (self.start_idx, self.end_idx),
                     },
                     &self.ll_line,
                 )
@@ -0,0 +1,192 @@
+use std::marker::PhantomData;
+
+use super::{x, LLCursorAssignment, LLSelection, Resolver};
+
+/// Combinators building new [Resolver]s out of existing ones
+///
+/// ```
+/// use layered_nlp::{create_line_from_string, x, ResolverExt, TextMatchAssignResolver};
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// enum Service {
+///     Slack,
+///     Algolia,
+/// }
+///
+/// let resolver = TextMatchAssignResolver::new_case_insensitive_str_arr([
+///     ("Slack", Service::Slack),
+///     ("Algolia", Service::Algolia),
+/// ])
+/// .filter(|_, service| *service != Service::Algolia);
+///
+/// let ll_line = create_line_from_string("Slack or Algolia").run(&resolver);
+///
+/// let services: Vec<_> = ll_line
+///     .find(&x::attr::<Service>())
+///     .into_iter()
+///     .map(|find| find.attr().clone())
+///     .collect();
+/// assert_eq!(services, vec![&Service::Slack]);
+/// ```
+pub trait ResolverExt: Resolver + Sized {
+    /// Assign `f(attr)` instead of each attribute
+    fn map<B, F>(self, f: F) -> Map<Self, F>
+    where
+        F: Fn(Self::Attr) -> B,
+    {
+        Map { resolver: self, f }
+    }
+
+    /// Only keep the assignments where `predicate` returns `true`
+    fn filter<F>(self, predicate: F) -> Filter<Self, F>
+    where
+        F: Fn(&LLSelection, &Self::Attr) -> bool,
+    {
+        Filter {
+            resolver: self,
+            predicate,
+        }
+    }
+
+    /// Run only inside the spans of `A` attributes assigned by previous resolvers, like clauses
+    ///
+    /// Overlapping spans are merged, so the resolver runs once over them.
+    fn restrict_to<A: 'static>(self) -> RestrictTo<Self, A> {
+        RestrictTo {
+            resolver: self,
+            attr: PhantomData,
+        }
+    }
+
+    /// Replace each assignment by those of `f`, which can run another resolver on the selection
+    fn and_then<B, F>(self, f: F) -> AndThen<Self, F>
+    where
+        F: Fn(LLSelection, Self::Attr) -> Vec<LLCursorAssignment<B>>,
+    {
+        AndThen { resolver: self, f }
+    }
+}
+
+impl<R: Resolver> ResolverExt for R {}
+
+/// Selection of the tokens assigned by `assignment`
+fn assignment_selection<A>(
+    selection: &LLSelection,
+    assignment: &LLCursorAssignment<A>,
+) -> LLSelection {
+    LLSelection {
+        ll_line: selection.ll_line.clone(),
+        start_idx: assignment.start_idx,
+        end_idx: assignment.end_idx,
+    }
+}
+
+/// See [ResolverExt::map]
+pub struct Map<R, F> {
+    resolver: R,
+    f: F,
+}
+
+impl<R, F, B> Resolver for Map<R, F>
+where
+    R: Resolver,
+    F: Fn(R::Attr) -> B,
+    B: std::fmt::Debug + 'static + Send + Sync,
+{
+    type Attr = B;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.resolver
+            .go(selection)
+            .into_iter()
+            .map(|assignment| LLCursorAssignment {
+                start_idx: assignment.start_idx,
+                end_idx: assignment.end_idx,
+                value: (self.f)(assignment.value),
+            })
+            .collect()
+    }
+}
+
+/// See [ResolverExt::filter]
+pub struct Filter<R, F> {
+    resolver: R,
+    predicate: F,
+}
+
+impl<R, F> Resolver for Filter<R, F>
+where
+    R: Resolver,
+    F: Fn(&LLSelection, &R::Attr) -> bool,
+{
+    type Attr = R::Attr;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.resolver
+            .go(selection.clone())
+            .into_iter()
+            .filter(|assignment| {
+                (self.predicate)(
+                    &assignment_selection(&selection, assignment),
+                    &assignment.value,
+                )
+            })
+            .collect()
+    }
+}
+
+/// See [ResolverExt::restrict_to]
+pub struct RestrictTo<R, A> {
+    resolver: R,
+    attr: PhantomData<fn() -> A>,
+}
+
+impl<R: Resolver, A: 'static> Resolver for RestrictTo<R, A> {
+    type Attr = R::Attr;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let mut spans: Vec<LLSelection> = Vec::new();
+        // found in start order, so only the last span can overlap the next ones
+        for (span, _) in selection.find_by(&x::attr::<A>()) {
+            match spans.last_mut() {
+                Some(last) if span.start_idx <= last.end_idx => {
+                    last.end_idx = last.end_idx.max(span.end_idx);
+                }
+                _ => spans.push(span),
+            }
+        }
+
+        spans
+            .into_iter()
+            .flat_map(|span| self.resolver.go(span))
+            .collect()
+    }
+}
+
+/// See [ResolverExt::and_then]
+pub struct AndThen<R, F> {
+    resolver: R,
+    f: F,
+}
+
+impl<R, F, B> Resolver for AndThen<R, F>
+where
+    R: Resolver,
+    F: Fn(LLSelection, R::Attr) -> Vec<LLCursorAssignment<B>>,
+    B: std::fmt::Debug + 'static + Send + Sync,
+{
+    type Attr = B;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        self.resolver
+            .go(selection.clone())
+            .into_iter()
+            .flat_map(|assignment| {
+                (self.f)(
+                    assignment_selection(&selection, &assignment),
+                    assignment.value,
+                )
+            })
+            .collect()
+    }
+}
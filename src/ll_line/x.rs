@@ -1,34 +1,64 @@
 //! # Matcher pieces
 
 mod all;
+mod anchor;
 mod any_of;
 mod attr;
 mod attr_eq;
+mod attr_text;
+mod capture;
+mod contains;
 mod functions;
+mod normalized_text;
+mod pattern;
 mod seq;
+mod seq_skip;
+mod spanned;
+mod then;
 mod token_has_any;
 mod token_text;
+mod value;
 
 pub use all::{All, All2, All3};
+pub use anchor::Anchor;
 pub use any_of::{AnyOf, AnyOf2, AnyOf2Matcher, AnyOf3, AnyOf3Matcher};
 pub use attr::Attr;
 pub use attr_eq::AttrEq;
-pub use functions::{all, any_of, attr, attr_eq, seq, token_has_any, token_text, whitespace};
+pub use attr_text::AttrText;
+pub use capture::{Capture, CaptureValue, Captured, Captures, CollectCaptures};
+pub use contains::{Multiple, XContains};
+pub use functions::{
+    all, any_of, attr, attr_eq, attr_text, attr_texts, capture, line_end, line_start, multiple,
+    multiple_of, normalized_text, selection_end, selection_start, seq, seq_ignoring_stopwords,
+    seq_skip, seq_spans, seq_ws, spanned, then_anywhere, then_within, token_has_any, token_text,
+    token_texts, value, whitespace, FindTexts,
+};
+pub use normalized_text::NormalizedText;
+pub use pattern::{
+    Pattern, PatternCapture, PatternCaptures, PatternError, PatternErrorKind, PatternRegistry,
+};
 pub use seq::{Seq, Seq2, Seq3};
+pub use seq_skip::{NotOnly, NotOnlyEach, SeqSkip, SeqSkip2, SeqSkip3};
+pub use spanned::{SeqSpans, Spanned, SpannedMatcher};
+pub use then::{Nothing, Then};
 pub use token_has_any::TokenHasAny;
 pub use token_text::TokenText;
+pub use value::Value;
 
-use super::{LLLine, LLToken, LToken};
+use std::any::TypeId;
+
+use super::{LLLine, LLToken, LRange, LToken, ValueToken};
 
 /// Examples: Attr, AttrEq
 pub trait XMatch<'l> {
-    /// Usually must be [Copy] so it's compatible with any multi-matchers.
-    /// The Out must be copied in the event of "cartesian" product scenarios where multi-matchers
+    /// Must be [Clone] so it's compatible with any multi-matchers.
+    /// The Out must be cloned in the event of "cartesian" product scenarios where multi-matchers
     /// return multiple combinations of their inner matchers' Out.
     ///
     /// This usually isn't a big deal to implement, since most Out values will be a reference
-    /// like `&'l Tag`, and all references in Rust are [Copy].
-    type Out: Copy;
+    /// like `&'l Tag`, which is [Copy]. Owned outputs, like the [PatternCaptures] of a [Pattern],
+    /// are cloned for each combination.
+    type Out: Clone;
 
     fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
     where
@@ -50,6 +80,8 @@ where
     //     todo!()
     // }
     fn attr<T: 'static>(&self, ll_line: &'l LLLine) -> Vec<(&'l T, ToIdx)>;
+    /// Like [XDirection::attr], for the attribute type `type_id`, returns the ranges of the attributes
+    fn attr_ranges(&self, type_id: TypeId, ll_line: &'l LLLine) -> Vec<(LRange, ToIdx)>;
     fn attr_eq<T: 'static + PartialEq>(&self, equals: &T, ll_line: &'l LLLine) -> Vec<((), ToIdx)>;
     fn token_attr_one_of<T: 'static + PartialEq>(
         &self,
@@ -58,40 +90,43 @@ where
     ) -> Vec<(&'l T, ToIdx)>;
     /// If the next token is Text, return the inner string slice
     fn text_token(&self, ll_line: &'l LLLine) -> Option<(&'l str, ToIdx)>;
+    /// If the next token is a Value, return its [ValueToken]
+    fn value_token(&self, ll_line: &'l LLLine) -> Option<(&'l ValueToken, ToIdx)>;
+    /// Indexes of the tokens right before and right after the current position, in line order.
+    ///
+    /// `None` at the start or at the end of the line.
+    fn neighbors(&self, ll_line: &'l LLLine) -> (Option<usize>, Option<usize>);
+    /// Bounds (start, end inclusive) of the selection the match started from
+    fn selection(&self) -> (usize, usize);
+    /// Match without consuming any token, for zero-width matchers like [line_start]
+    fn zero_width(&self) -> ToIdx;
+    /// Tokens (start, end inclusive, in line order) taken by a match from here to `to_idx`.
+    ///
+    /// `None` for zero-width matches.
+    fn span_to(&self, to_idx: ToIdx) -> Option<(usize, usize)>;
+    /// Continue after the token at `idx`.
+    ///
+    /// The position after the last token (or before the first one going backwards)
+    /// is still a direction, where only zero-width matchers can match.
     fn after(&self, idx: usize, ll_line: &'l LLLine) -> Option<Self>
     where
         Self: Sized;
 }
 
 pub(crate) struct XForwards {
+    /// Next token to match, the length of the line after its last token
     pub(super) from_idx: usize,
+    pub(super) selection: (usize, usize),
 }
 
-// TODO:
-//        ╰─╯Amount(1000.25)
-//  ╰───╯Person(A)
-//                 ╰───╯Person(B)
-//           ╰───╯VerbPhrase
-// selection
-//  .find_by(attr::<Clause>())
-//  .iter ... .map
-//      .contains_in_any_order(&(multiple::<Person, 2>(), attr::<Amount>(), attr::<VerbPhrase>()))
-//        -> Vec of 2 -> Full original selection
-//             - ([&Person(A), &Person(B)], &Amount, &VerbPhrase)
-// pub(crate) struct XContains {
-//     pub(super) start_idx: usize,
-//     pub(super) end_idx: usize,
-// }
-
 impl<'l> XDirection<'l> for XForwards {
     fn attr_eq<T: 'static + PartialEq>(&self, equals: &T, ll_line: &'l LLLine) -> Vec<((), ToIdx)> {
         ll_line
             .attrs
             .starts_at
             .get(self.from_idx)
-            .expect("Huh... match_forwards was at the end")
-            .get::<T>()
-            .iter()
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get::<T>())
             .flat_map(|range| {
                 ll_line
                     .attrs
@@ -116,9 +151,8 @@ impl<'l> XDirection<'l> for XForwards {
             .attrs
             .starts_at
             .get(self.from_idx)
-            .expect("Huh... match_forwards was at the end")
-            .get::<T>()
-            .iter()
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get::<T>())
             .flat_map(|range| {
                 ll_line
                     .attrs
@@ -132,6 +166,17 @@ impl<'l> XDirection<'l> for XForwards {
             .collect()
     }
 
+    fn attr_ranges(&self, type_id: TypeId, ll_line: &'l LLLine) -> Vec<(LRange, ToIdx)> {
+        ll_line
+            .attrs
+            .starts_at
+            .get(self.from_idx)
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get_any(type_id))
+            .map(|range| (*range, ToIdx(range.1)))
+            .collect()
+    }
+
     fn token_attr_one_of<T: 'static + PartialEq>(
         &self,
         set: &[T],
@@ -143,9 +188,8 @@ impl<'l> XDirection<'l> for XForwards {
             .attrs
             .values
             .get(&(self.from_idx, self.from_idx))
-            .expect("Huh... match_forwards was at the end")
-            .get::<T>()
-            .iter()
+            .into_iter()
+            .flat_map(|values| values.get::<T>())
             .filter_map(|value| {
                 if set.contains(value) {
                     Some((value, ToIdx(self.from_idx)))
@@ -159,23 +203,52 @@ impl<'l> XDirection<'l> for XForwards {
     fn text_token(&self, ll_line: &'l LLLine) -> Option<(&'l str, ToIdx)> {
         // [ ... ] - Current Selection
         //        [ ... ] - Trying to match Attr
-        match ll_line
-            .ll_tokens
-            .get(self.from_idx)
-            .expect("Huh... XForwards::text_token was out of LLLine")
-        {
-            LLToken {
+        match ll_line.ll_tokens.get(self.from_idx) {
+            Some(LLToken {
                 token: LToken::Text(s, _),
                 ..
-            } => Some((s, ToIdx(self.from_idx))),
+            }) => Some((s, ToIdx(self.from_idx))),
             _ => None,
         }
     }
 
+    fn value_token(&self, ll_line: &'l LLLine) -> Option<(&'l ValueToken, ToIdx)> {
+        match ll_line.ll_tokens.get(self.from_idx) {
+            Some(LLToken {
+                token: LToken::Value(value),
+                ..
+            }) => Some((value, ToIdx(self.from_idx))),
+            _ => None,
+        }
+    }
+
+    fn neighbors(&self, ll_line: &'l LLLine) -> (Option<usize>, Option<usize>) {
+        (
+            self.from_idx.checked_sub(1),
+            Some(self.from_idx).filter(|idx| *idx < ll_line.ll_tokens.len()),
+        )
+    }
+
+    fn selection(&self) -> (usize, usize) {
+        self.selection
+    }
+
+    fn zero_width(&self) -> ToIdx {
+        // wraps around before the first token, see `after`
+        ToIdx(self.from_idx.wrapping_sub(1))
+    }
+
+    fn span_to(&self, to_idx: ToIdx) -> Option<(usize, usize)> {
+        Some((self.from_idx, to_idx.0)).filter(|_| to_idx != self.zero_width())
+    }
+
     fn after(&self, idx: usize, ll_line: &'l LLLine) -> Option<Self> {
-        let next_idx = idx + 1;
-        if next_idx < ll_line.ll_tokens.len() {
-            Some(XForwards { from_idx: next_idx })
+        let next_idx = idx.wrapping_add(1);
+        if next_idx <= ll_line.ll_tokens.len() {
+            Some(XForwards {
+                from_idx: next_idx,
+                selection: self.selection,
+            })
         } else {
             None
         }
@@ -183,7 +256,9 @@ impl<'l> XDirection<'l> for XForwards {
 }
 
 pub(crate) struct XBackwards {
+    /// Next token to match, [usize::MAX] before the first token
     pub(super) from_idx: usize,
+    pub(super) selection: (usize, usize),
 }
 
 impl<'l> XDirection<'l> for XBackwards {
@@ -195,9 +270,8 @@ impl<'l> XDirection<'l> for XBackwards {
             .attrs
             .ends_at
             .get(self.from_idx)
-            .expect("Huh... match_backwards was out of LLLine")
-            .get::<T>()
-            .iter()
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get::<T>())
             .flat_map(|range| {
                 ll_line
                     .attrs
@@ -225,9 +299,8 @@ impl<'l> XDirection<'l> for XBackwards {
             .attrs
             .ends_at
             .get(self.from_idx)
-            .expect("Huh... Backwards::next_attr was at the start")
-            .get::<T>()
-            .iter()
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get::<T>())
             .flat_map(|range| {
                 ll_line
                     .attrs
@@ -241,6 +314,17 @@ impl<'l> XDirection<'l> for XBackwards {
             .collect()
     }
 
+    fn attr_ranges(&self, type_id: TypeId, ll_line: &'l LLLine) -> Vec<(LRange, ToIdx)> {
+        ll_line
+            .attrs
+            .ends_at
+            .get(self.from_idx)
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get_any(type_id))
+            .map(|range| (*range, ToIdx(range.0)))
+            .collect()
+    }
+
     fn token_attr_one_of<T: 'static + PartialEq>(
         &self,
         set: &[T],
@@ -253,9 +337,8 @@ impl<'l> XDirection<'l> for XBackwards {
             .attrs
             .ends_at
             .get(self.from_idx)
-            .expect("Huh... Backwards::next_attr was at the start")
-            .get::<T>()
-            .iter()
+            .into_iter()
+            .flat_map(|at_idx| at_idx.get::<T>())
             .flat_map(|range| {
                 ll_line
                     .attrs
@@ -281,13 +364,45 @@ impl<'l> XDirection<'l> for XBackwards {
             .get(self.from_idx)
             .and_then(|token| match &token.token {
                 LToken::Text(text, _) => Some((text.as_str(), ToIdx(self.from_idx))),
-                LToken::Value => None,
+                LToken::Value(_) => None,
             })
     }
 
+    fn value_token(&self, ll_line: &'l LLLine) -> Option<(&'l ValueToken, ToIdx)> {
+        ll_line
+            .ll_tokens()
+            .get(self.from_idx)
+            .and_then(|token| match &token.token {
+                LToken::Value(value) => Some((value, ToIdx(self.from_idx))),
+                LToken::Text(..) => None,
+            })
+    }
+
+    fn neighbors(&self, ll_line: &'l LLLine) -> (Option<usize>, Option<usize>) {
+        (
+            Some(self.from_idx).filter(|idx| *idx != usize::MAX),
+            Some(self.from_idx.wrapping_add(1)).filter(|idx| *idx < ll_line.ll_tokens.len()),
+        )
+    }
+
+    fn selection(&self) -> (usize, usize) {
+        self.selection
+    }
+
+    fn zero_width(&self) -> ToIdx {
+        ToIdx(self.from_idx.wrapping_add(1))
+    }
+
+    fn span_to(&self, to_idx: ToIdx) -> Option<(usize, usize)> {
+        Some((to_idx.0, self.from_idx)).filter(|_| to_idx != self.zero_width())
+    }
+
     fn after(&self, idx: usize, _: &'l LLLine) -> Option<Self> {
-        if idx > 0 {
-            Some(XBackwards { from_idx: idx - 1 })
+        if idx != usize::MAX {
+            Some(XBackwards {
+                from_idx: idx.wrapping_sub(1),
+                selection: self.selection,
+            })
         } else {
             None
         }
@@ -44,7 +44,7 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>> XMatch<'l> for All2<A, B> {
             .flat_map(|(a, a_to_idx)| {
                 bs.iter().filter_map(move |(b, b_to_idx)| {
                     if a_to_idx == *b_to_idx {
-                        Some(((a, *b), a_to_idx))
+                        Some(((a.clone(), b.clone()), a_to_idx))
                     } else {
                         None
                     }
@@ -83,9 +83,10 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>, C: XMatch<'l>> XMatch<'l> for All3<A, B,
             .flat_map(|(a, a_to_idx)| {
                 let cs_iter = cs.iter();
                 bs.iter().flat_map(move |(b, b_to_idx)| {
+                    let a = a.clone();
                     cs_iter.clone().filter_map(move |(c, c_to_idx)| {
                         if &a_to_idx == b_to_idx && &a_to_idx == c_to_idx {
-                            Some(((a, *b, *c), a_to_idx))
+                            Some(((a.clone(), b.clone(), c.clone()), a_to_idx))
                         } else {
                             None
                         }
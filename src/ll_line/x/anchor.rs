@@ -0,0 +1,37 @@
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+#[derive(Clone, Copy)]
+pub(crate) enum AnchorKind {
+    LineStart,
+    LineEnd,
+    SelectionStart,
+    SelectionEnd,
+}
+
+/// Zero-width matcher, see [line_start](super::line_start)
+pub struct Anchor(pub(crate) AnchorKind);
+
+impl<'l> XMatch<'l> for Anchor {
+    type Out = ();
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        let (before, after) = direction.neighbors(ll_line);
+        let (selection_start, selection_end) = direction.selection();
+
+        let matches = match self.0 {
+            AnchorKind::LineStart => before.is_none(),
+            AnchorKind::LineEnd => after.is_none(),
+            AnchorKind::SelectionStart => after == Some(selection_start),
+            AnchorKind::SelectionEnd => before == Some(selection_end),
+        };
+
+        if matches {
+            vec![((), direction.zero_width())]
+        } else {
+            Vec::new()
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+pub struct AttrText<A>(pub(crate) std::marker::PhantomData<A>);
+
+impl<'l, A: AsRef<str> + 'static> XMatch<'l> for AttrText<A> {
+    type Out = &'l str;
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        direction
+            .attr::<A>(ll_line)
+            .into_iter()
+            .map(|(attr, to_idx)| (attr.as_ref(), to_idx))
+            .collect()
+    }
+}
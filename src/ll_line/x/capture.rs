@@ -0,0 +1,283 @@
+use std::any::Any;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use super::{
+    AnyOf2, AnyOf3, LLLine, PatternCapture, Spanned, SpannedMatcher, ToIdx, XDirection, XMatch,
+};
+use crate::LLSelection;
+
+/// Named matcher, see [capture](super::capture)
+pub struct Capture<M> {
+    pub(crate) name: &'static str,
+    pub(crate) matcher: SpannedMatcher<M>,
+}
+
+/// Output of a [Capture], the [Spanned] output of its matcher with the name of the capture
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Captured<V> {
+    pub name: &'static str,
+    pub spanned: Spanned<V>,
+}
+
+impl<'l, M: XMatch<'l>> XMatch<'l> for Capture<M> {
+    type Out = Captured<M::Out>;
+
+    fn go<D>(&self, direction: &D, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        D: XDirection<'l>,
+    {
+        self.matcher
+            .go(direction, ll_line)
+            .into_iter()
+            .map(|(spanned, to_idx)| {
+                let captured = Captured {
+                    name: self.name,
+                    spanned,
+                };
+                (captured, to_idx)
+            })
+            .collect()
+    }
+}
+
+/// Captured output types which can be looked up in [Captures]
+pub trait CaptureValue<'l>: Copy {
+    /// For [Captures::get]
+    fn as_any(self) -> Option<&'l dyn Any> {
+        None
+    }
+
+    /// For [Captures::text]
+    fn as_text(self) -> Option<&'l str> {
+        None
+    }
+}
+
+impl<'l, T: Any> CaptureValue<'l> for &'l T {
+    fn as_any(self) -> Option<&'l dyn Any> {
+        Some(self)
+    }
+}
+
+impl<'l> CaptureValue<'l> for &'l str {
+    fn as_text(self) -> Option<&'l str> {
+        Some(self)
+    }
+}
+
+impl<'l> CaptureValue<'l> for () {}
+
+impl<'l, V: CaptureValue<'l>> CaptureValue<'l> for Captured<V> {
+    fn as_any(self) -> Option<&'l dyn Any> {
+        self.spanned.value.as_any()
+    }
+
+    fn as_text(self) -> Option<&'l str> {
+        self.spanned.value.as_text()
+    }
+}
+
+impl<'l, A: Copy, B: Copy> CaptureValue<'l> for (A, B) {}
+
+impl<'l, A: Copy, B: Copy, C: Copy> CaptureValue<'l> for (A, B, C) {}
+
+impl<'l, A: CaptureValue<'l>, B: CaptureValue<'l>> CaptureValue<'l> for AnyOf2<A, B> {
+    fn as_any(self) -> Option<&'l dyn Any> {
+        match self {
+            AnyOf2::A(a) => a.as_any(),
+            AnyOf2::B(b) => b.as_any(),
+        }
+    }
+
+    fn as_text(self) -> Option<&'l str> {
+        match self {
+            AnyOf2::A(a) => a.as_text(),
+            AnyOf2::B(b) => b.as_text(),
+        }
+    }
+}
+
+impl<'l, A: CaptureValue<'l>, B: CaptureValue<'l>, C: CaptureValue<'l>> CaptureValue<'l>
+    for AnyOf3<A, B, C>
+{
+    fn as_any(self) -> Option<&'l dyn Any> {
+        match self {
+            AnyOf3::A(a) => a.as_any(),
+            AnyOf3::B(b) => b.as_any(),
+            AnyOf3::C(c) => c.as_any(),
+        }
+    }
+
+    fn as_text(self) -> Option<&'l str> {
+        match self {
+            AnyOf3::A(a) => a.as_text(),
+            AnyOf3::B(b) => b.as_text(),
+            AnyOf3::C(c) => c.as_text(),
+        }
+    }
+}
+
+/// Matcher outputs which may contain [Captured] values
+pub trait CollectCaptures<'l> {
+    fn collect_captures(&self, captures: &mut Captures<'l>);
+}
+
+impl<'l, V: CaptureValue<'l> + CollectCaptures<'l>> CollectCaptures<'l> for Captured<V> {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        let Spanned { value, span } = self.spanned;
+        captures.captured.push(CapturedEntry {
+            name: self.name.into(),
+            span,
+            any: value.as_any(),
+            text: value.as_text(),
+        });
+        value.collect_captures(captures);
+    }
+}
+
+impl<'l> CollectCaptures<'l> for PatternCapture {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        captures.captured.push(CapturedEntry {
+            name: self.name.clone(),
+            span: self.spanned.span,
+            any: None,
+            text: None,
+        });
+    }
+}
+
+impl<'l, T: ?Sized> CollectCaptures<'l> for &T {
+    fn collect_captures(&self, _: &mut Captures<'l>) {}
+}
+
+impl<'l> CollectCaptures<'l> for () {
+    fn collect_captures(&self, _: &mut Captures<'l>) {}
+}
+
+impl<'l, A: CollectCaptures<'l>, B: CollectCaptures<'l>> CollectCaptures<'l> for (A, B) {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        self.0.collect_captures(captures);
+        self.1.collect_captures(captures);
+    }
+}
+
+impl<'l, A: CollectCaptures<'l>, B: CollectCaptures<'l>, C: CollectCaptures<'l>> CollectCaptures<'l>
+    for (A, B, C)
+{
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        self.0.collect_captures(captures);
+        self.1.collect_captures(captures);
+        self.2.collect_captures(captures);
+    }
+}
+
+impl<'l, A: CollectCaptures<'l>, B: CollectCaptures<'l>> CollectCaptures<'l> for AnyOf2<A, B> {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        match self {
+            AnyOf2::A(a) => a.collect_captures(captures),
+            AnyOf2::B(b) => b.collect_captures(captures),
+        }
+    }
+}
+
+impl<'l, A: CollectCaptures<'l>, B: CollectCaptures<'l>, C: CollectCaptures<'l>> CollectCaptures<'l>
+    for AnyOf3<A, B, C>
+{
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        match self {
+            AnyOf3::A(a) => a.collect_captures(captures),
+            AnyOf3::B(b) => b.collect_captures(captures),
+            AnyOf3::C(c) => c.collect_captures(captures),
+        }
+    }
+}
+
+impl<'l, T: CollectCaptures<'l>> CollectCaptures<'l> for Option<T> {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        if let Some(inner) = self {
+            inner.collect_captures(captures);
+        }
+    }
+}
+
+impl<'l, T: CollectCaptures<'l>> CollectCaptures<'l> for Vec<T> {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        for item in self {
+            item.collect_captures(captures);
+        }
+    }
+}
+
+impl<'l, T: CollectCaptures<'l>, const N: usize> CollectCaptures<'l> for [T; N] {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        for item in self {
+            item.collect_captures(captures);
+        }
+    }
+}
+
+struct CapturedEntry<'l> {
+    name: Arc<str>,
+    span: Option<(usize, usize)>,
+    any: Option<&'l dyn Any>,
+    text: Option<&'l str>,
+}
+
+/// Sub-selections and values of the [Capture]s of a match, by name.
+///
+/// When a name is captured more than once, lookups return the first capture in the matcher.
+pub struct Captures<'l> {
+    ll_line: Rc<LLLine>,
+    captured: Vec<CapturedEntry<'l>>,
+}
+
+impl<'l> Captures<'l> {
+    pub(crate) fn new(ll_line: Rc<LLLine>) -> Self {
+        Captures {
+            ll_line,
+            captured: Vec::new(),
+        }
+    }
+
+    fn entry(&self, name: &str) -> Option<&CapturedEntry<'l>> {
+        self.captured.iter().find(|entry| &*entry.name == name)
+    }
+
+    /// Names of the captures, in matcher order
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.captured.iter().map(|entry| &*entry.name)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entry(name).is_some()
+    }
+
+    /// Tokens taken by the capture, `None` if it wasn't matched or didn't take any token
+    pub fn selection(&self, name: &str) -> Option<LLSelection> {
+        let (start_idx, end_idx) = self.entry(name)?.span?;
+        Some(LLSelection {
+            ll_line: self.ll_line.clone(),
+            start_idx,
+            end_idx,
+        })
+    }
+
+    /// Value of a capture with a `&T` output, like [attr](super::attr) or [value](super::value)
+    pub fn get<T: Any>(&self, name: &str) -> Option<&'l T> {
+        self.entry(name)?.any?.downcast_ref()
+    }
+
+    /// Value of a capture with a `&str` output, like [token_text](super::token_text)
+    pub fn text(&self, name: &str) -> Option<&'l str> {
+        self.entry(name)?.text
+    }
+}
+
+impl std::fmt::Debug for Captures<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_map()
+            .entries(self.captured.iter().map(|entry| (&*entry.name, entry.span)))
+            .finish()
+    }
+}
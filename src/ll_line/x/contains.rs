@@ -0,0 +1,134 @@
+use super::{LLLine, XForwards, XMatch};
+
+/// Token spans (start, end inclusive) taken by a match
+type Spans = Vec<(usize, usize)>;
+
+fn overlaps(a: &[(usize, usize)], b: &[(usize, usize)]) -> bool {
+    a.iter()
+        .any(|(a0, a1)| b.iter().any(|(b0, b1)| a0 <= b1 && b0 <= a1))
+}
+
+/// Matches found anywhere inside a selection, in any order, see [LLSelection::contains_in_any_order](crate::LLSelection::contains_in_any_order)
+///
+/// Implemented for every [XMatch], for [Multiple], and for tuples of those.
+pub trait XContains<'l> {
+    type Out: Clone;
+
+    /// Every match inside `selection` (start, end inclusive), with the token spans it takes
+    fn contained(&self, selection: (usize, usize), ll_line: &'l LLLine) -> Vec<(Self::Out, Spans)>;
+}
+
+impl<'l, T: XMatch<'l>> XContains<'l> for T {
+    type Out = T::Out;
+
+    fn contained(&self, selection: (usize, usize), ll_line: &'l LLLine) -> Vec<(Self::Out, Spans)> {
+        (selection.0..=selection.1)
+            .flat_map(|i| {
+                let forwards = XForwards {
+                    from_idx: i,
+                    selection,
+                };
+
+                self.go(&forwards, ll_line)
+                    .into_iter()
+                    // zero-width matches don't take any token
+                    .filter(move |(_, to_idx)| (i..=selection.1).contains(&to_idx.0))
+                    .map(move |(out, to_idx)| (out, vec![(i, to_idx.0)]))
+            })
+            .collect()
+    }
+}
+
+/// `N` distinct, non-overlapping matches of the inner matcher, in line order
+pub struct Multiple<M, const N: usize>(pub M);
+
+impl<'l, M: XMatch<'l>, const N: usize> XContains<'l> for Multiple<M, N> {
+    type Out = [M::Out; N];
+
+    fn contained(&self, selection: (usize, usize), ll_line: &'l LLLine) -> Vec<(Self::Out, Spans)> {
+        let candidates = self.0.contained(selection, ll_line);
+        let mut combinations = Vec::new();
+        let mut picked = Vec::with_capacity(N);
+        pick(
+            N,
+            &candidates,
+            0,
+            &mut picked,
+            &mut Vec::new(),
+            &mut |picked, spans| {
+                let out = std::array::from_fn(|i| candidates[picked[i]].0.clone());
+                combinations.push((out, spans.to_vec()));
+            },
+        );
+        combinations
+    }
+}
+
+/// Calls `found` with every combination of `n` candidates taking distinct tokens
+fn pick<O>(
+    n: usize,
+    candidates: &[(O, Spans)],
+    from: usize,
+    picked: &mut Vec<usize>,
+    spans: &mut Spans,
+    found: &mut impl FnMut(&[usize], &[(usize, usize)]),
+) {
+    if picked.len() == n {
+        found(picked, spans);
+        return;
+    }
+
+    for (idx, (_, candidate_spans)) in candidates.iter().enumerate().skip(from) {
+        if overlaps(spans, candidate_spans) {
+            continue;
+        }
+        let spans_len = spans.len();
+        picked.push(idx);
+        spans.extend_from_slice(candidate_spans);
+        pick(n, candidates, idx + 1, picked, spans, found);
+        picked.pop();
+        spans.truncate(spans_len);
+    }
+}
+
+/// Pairs of `a` and `b` matches which don't take the same tokens
+fn disjoint_pairs<A: Clone, B: Clone>(
+    a: Vec<(A, Spans)>,
+    b: &[(B, Spans)],
+) -> Vec<((A, B), Spans)> {
+    let mut pairs = Vec::new();
+    for (a, a_spans) in a {
+        for (b, b_spans) in b {
+            if !overlaps(&a_spans, b_spans) {
+                pairs.push(((a.clone(), b.clone()), [&a_spans[..], b_spans].concat()));
+            }
+        }
+    }
+    pairs
+}
+
+impl<'l, A: XContains<'l>, B: XContains<'l>> XContains<'l> for (A, B) {
+    type Out = (A::Out, B::Out);
+
+    fn contained(&self, selection: (usize, usize), ll_line: &'l LLLine) -> Vec<(Self::Out, Spans)> {
+        disjoint_pairs(
+            self.0.contained(selection, ll_line),
+            &self.1.contained(selection, ll_line),
+        )
+    }
+}
+
+impl<'l, A: XContains<'l>, B: XContains<'l>, C: XContains<'l>> XContains<'l> for (A, B, C) {
+    type Out = (A::Out, B::Out, C::Out);
+
+    fn contained(&self, selection: (usize, usize), ll_line: &'l LLLine) -> Vec<(Self::Out, Spans)> {
+        let ab = disjoint_pairs(
+            self.0.contained(selection, ll_line),
+            &self.1.contained(selection, ll_line),
+        );
+        disjoint_pairs(ab, &self.2.contained(selection, ll_line))
+            .into_iter()
+            .map(|(((a, b), c), spans)| ((a, b, c), spans))
+            .collect()
+    }
+}
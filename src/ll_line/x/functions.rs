@@ -1,5 +1,6 @@
+use super::anchor::AnchorKind;
 use super::*;
-use crate::TextTag;
+use crate::{LLSelection, Stopword, TextTag};
 
 /// Match if one of the matchers match
 pub fn any_of<T: AnyOf>(tuple: T) -> T::Out {
@@ -24,11 +25,122 @@ pub fn seq<T: Seq>(tuple: T) -> T::Out {
     tuple.into_seq()
 }
 
+/// Like [seq], outputting the [Spanned] output of each matcher
+///
+/// Use [Spanned::selection] to get the sub-selection matched by each element.
+pub fn seq_spans<T: SeqSpans>(tuple: T) -> T::Out {
+    tuple.into_seq_spans()
+}
+
+/// Output the tokens taken by `matcher` along with its output, see [Spanned]
+pub fn spanned<M>(matcher: M) -> SpannedMatcher<M> {
+    SpannedMatcher(matcher)
+}
+
+/// Match all matchers one after the other, allowing any number of tokens matched by `skip` between them
+///
+/// Example going forward, with `s` the skipped tokens:
+///
+/// ```txt
+/// [ Matcher #1 ]
+///               s s [ Matcher #2 ]
+///                                 [ Matcher #3 ]
+/// ```
+pub fn seq_skip<T: SeqSkip<S>, S>(tuple: T, skip: S) -> T::Out {
+    tuple.into_seq_skip(skip)
+}
+
+/// Match all matchers one after the other, allowing whitespace between them
+///
+/// `x::seq_ws((a, b))` matches both `ab` and `a   b`, and outputs the same as `x::seq((a, b))`.
+pub fn seq_ws<T: SeqSkip<AttrEq<'static, TextTag>>>(tuple: T) -> T::Out {
+    tuple.into_seq_skip(whitespace())
+}
+
+/// Match all matchers one after the other, ignoring whitespace and [Stopword]s between them
+///
+/// Matches of the matchers made of [Stopword]s only are ignored as well.
+/// Needs the [StopwordResolver](crate::StopwordResolver) to have run.
+pub fn seq_ignoring_stopwords<T>(
+    tuple: T,
+) -> <T::Out as SeqSkip<AnyOf2Matcher<AttrEq<'static, TextTag>, Attr<Stopword>>>>::Out
+where
+    T: NotOnlyEach<Stopword>,
+    T::Out: SeqSkip<AnyOf2Matcher<AttrEq<'static, TextTag>, Attr<Stopword>>>,
+{
+    seq_skip(
+        tuple.not_only_each(),
+        any_of((whitespace(), attr::<Stopword>())),
+    )
+}
+
+/// Match `a`, then `b` with at most `max_tokens` tokens (whitespace included) between them
+///
+/// Example going forward, with `max_tokens` of 2 and `s` the skipped tokens:
+///
+/// ```txt
+/// [ Matcher #1 ]
+///               s s [ Matcher #2 ]
+/// ```
+///
+/// Use [Then::not_crossing] to stop at tokens like punctuation.
+pub fn then_within<A, B>(a: A, b: B, max_tokens: usize) -> Then<A, B> {
+    Then::new(a, b, Some(max_tokens))
+}
+
+/// Match `a`, then `b` anywhere after it, see [then_within]
+pub fn then_anywhere<A, B>(a: A, b: B) -> Then<A, B> {
+    Then::new(a, b, None)
+}
+
+/// Name the match of `matcher`, to find its sub-selection and value with [LLSelection::find_captures](crate::LLSelection::find_captures)
+///
+/// Outputs a [Captured] wrapping the [Spanned] output of `matcher`.
+pub fn capture<M>(name: &'static str, matcher: M) -> Capture<M> {
+    Capture {
+        name,
+        matcher: SpannedMatcher(matcher),
+    }
+}
+
 /// Match single token and provide their text representation
 pub fn token_text() -> TokenText {
     TokenText(())
 }
 
+/// Match single token and provide their [Normalized](crate::Normalized) text, see [NormalizeResolver](crate::NormalizeResolver)
+pub fn normalized_text() -> NormalizedText {
+    NormalizedText(())
+}
+
+/// Match custom tokens with a `T` payload, see [InputToken::value](crate::InputToken::value)
+pub fn value<T>() -> Value<T> {
+    Value(Default::default())
+}
+
+/// Match at the start of the line, without consuming any token
+///
+/// Zero-width matchers only make sense as part of a [seq], like `x::seq((x::line_start(), x::token_text()))`
+/// for the first token of the line. They work going forwards and backwards.
+pub fn line_start() -> Anchor {
+    Anchor(AnchorKind::LineStart)
+}
+
+/// Match at the end of the line, without consuming any token, see [line_start]
+pub fn line_end() -> Anchor {
+    Anchor(AnchorKind::LineEnd)
+}
+
+/// Match right before the first token of the selection the match started from, see [line_start]
+pub fn selection_start() -> Anchor {
+    Anchor(AnchorKind::SelectionStart)
+}
+
+/// Match right after the last token of the selection the match started from, see [line_start]
+pub fn selection_end() -> Anchor {
+    Anchor(AnchorKind::SelectionEnd)
+}
+
 /// Match token with `A` attributes equal to `attr`
 pub fn attr_eq<A>(attr: &A) -> AttrEq<'_, A> {
     AttrEq { attr }
@@ -44,6 +156,36 @@ pub fn attr<A>() -> Attr<A> {
     Attr(Default::default())
 }
 
+/// Match `N` distinct `A` attributes, for [LLSelection::contains_in_any_order](crate::LLSelection::contains_in_any_order)
+///
+/// Outputs `[&A; N]` in line order.
+pub fn multiple<A, const N: usize>() -> Multiple<Attr<A>, N> {
+    Multiple(attr())
+}
+
+/// Match `N` distinct, non-overlapping matches of `matcher`, see [multiple]
+pub fn multiple_of<const N: usize, M>(matcher: M) -> Multiple<M, N> {
+    Multiple(matcher)
+}
+
+/// Match token with `A` attributes holding text, like [Normalized](crate::Normalized), and provide that text
+pub fn attr_text<A: AsRef<str>>() -> AttrText<A> {
+    AttrText(Default::default())
+}
+
+/// Finds texts within a selection, like [token_texts] or [attr_texts]
+pub type FindTexts = for<'a> fn(&'a LLSelection) -> Vec<(LLSelection, &'a str)>;
+
+/// Text of each token of `selection`, see [token_text]
+pub fn token_texts(selection: &LLSelection) -> Vec<(LLSelection, &str)> {
+    selection.find_by(&token_text())
+}
+
+/// Text of each `A` attribute of `selection`, see [attr_text]
+pub fn attr_texts<A: AsRef<str> + 'static>(selection: &LLSelection) -> Vec<(LLSelection, &str)> {
+    selection.find_by(&attr_text::<A>())
+}
+
 /// Match any number of consecutive spaces.
 pub fn whitespace() -> AttrEq<'static, TextTag> {
     attr_eq(&TextTag::SPACE)
@@ -0,0 +1,19 @@
+use super::{LLLine, ToIdx, XDirection, XMatch};
+use crate::Normalized;
+
+pub struct NormalizedText(pub(crate) ());
+
+impl<'l> XMatch<'l> for NormalizedText {
+    type Out = &'l str;
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        direction
+            .attr::<Normalized>(ll_line)
+            .into_iter()
+            .map(|(normalized, to_idx)| (normalized.as_str(), to_idx))
+            .collect()
+    }
+}
@@ -0,0 +1,833 @@
+//! Patterns written as text, like `[CurrencySymbol] _? [Amount]` or `"as" "soon" "as"`.
+
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::seq_skip::after_skipping;
+use super::{whitespace, LLLine, LRange, Spanned, ToIdx, XDirection, XMatch};
+use crate::TextTag;
+
+type AttrCheck = Arc<dyn Fn(&LLLine, LRange) -> bool + Send + Sync>;
+
+struct RegisteredAttr {
+    name: String,
+    type_id: TypeId,
+    /// Checks the values of the attributes at a range, for [PatternRegistry::register_value]
+    matches: AttrCheck,
+}
+
+/// Attribute types which patterns can refer to by name, see [Pattern]
+#[derive(Clone)]
+pub struct PatternRegistry {
+    attrs: HashMap<String, Arc<RegisteredAttr>>,
+}
+
+impl Default for PatternRegistry {
+    /// Registry with the [TextTag]s, like `[WORD]` or `[PUNC]`
+    fn default() -> Self {
+        PatternRegistry::new()
+            .register_value("NATN", TextTag::NATN)
+            .register_value("PUNC", TextTag::PUNC)
+            .register_value("SYMB", TextTag::SYMB)
+            .register_value("SPACE", TextTag::SPACE)
+            .register_value("WORD", TextTag::WORD)
+            .register_value("NEWLINE", TextTag::NEWLINE)
+    }
+}
+
+impl fmt::Debug for PatternRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.attrs.keys().collect();
+        names.sort();
+        f.debug_set().entries(names).finish()
+    }
+}
+
+impl PatternRegistry {
+    /// Empty registry
+    pub fn new() -> Self {
+        PatternRegistry {
+            attrs: HashMap::new(),
+        }
+    }
+
+    /// `[name]` matches any `T` attribute
+    pub fn register<T: 'static>(mut self, name: impl Into<String>) -> Self {
+        self.insert::<T>(name.into(), |_, _| true);
+        self
+    }
+
+    /// `[name]` matches `T` attributes equal to `value`
+    pub fn register_value<T>(mut self, name: impl Into<String>, value: T) -> Self
+    where
+        T: 'static + PartialEq + Send + Sync,
+    {
+        self.insert::<T>(name.into(), move |ll_line, range| {
+            ll_line
+                .attrs
+                .values
+                .get(&range)
+                .is_some_and(|values| values.get::<T>().contains(&value))
+        });
+        self
+    }
+
+    fn insert<T: 'static>(
+        &mut self,
+        name: String,
+        matches: impl Fn(&LLLine, LRange) -> bool + Send + Sync + 'static,
+    ) {
+        let attr = RegisteredAttr {
+            name: name.clone(),
+            type_id: TypeId::of::<T>(),
+            matches: Arc::new(matches),
+        };
+        self.attrs.insert(name, Arc::new(attr));
+    }
+}
+
+/// Why a pattern couldn't be parsed, see [PatternError]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatternErrorKind {
+    /// There is nothing to match
+    Empty,
+    /// The pattern ended in the middle of an element, like `[WORD`
+    UnexpectedEnd {
+        expected: &'static str,
+    },
+    UnexpectedChar {
+        found: char,
+        expected: &'static str,
+    },
+    /// `[...]` names an attribute which isn't in the [PatternRegistry]
+    UnknownAttribute(String),
+    /// `?`, `*` or `+` don't follow an element
+    NothingToRepeat(char),
+    /// `"..."` matches a single token, so it can't hold whitespace
+    WhitespaceInText,
+}
+
+/// Error from [Pattern::parse], pointing at the pattern column (in chars, starting at 1)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternError {
+    pub kind: PatternErrorKind,
+    pub column: usize,
+    /// Number of chars the error points at
+    pub width: usize,
+    pub pattern: String,
+}
+
+impl fmt::Display for PatternError {
+    /// Message followed by the pattern, with the error underlined
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            PatternErrorKind::Empty => write!(f, "Empty pattern")?,
+            PatternErrorKind::UnexpectedEnd { expected } => {
+                write!(f, "Pattern ended, expected {}", expected)?
+            }
+            PatternErrorKind::UnexpectedChar { found, expected } => {
+                write!(f, "Unexpected `{}`, expected {}", found, expected)?
+            }
+            PatternErrorKind::UnknownAttribute(name) => write!(f, "Unknown attribute `{}`", name)?,
+            PatternErrorKind::NothingToRepeat(quantifier) => {
+                write!(f, "Nothing to repeat before `{}`", quantifier)?
+            }
+            PatternErrorKind::WhitespaceInText => write!(
+                f,
+                "Text matches a single token and can't contain whitespace, quote each word instead"
+            )?,
+        }
+        write!(
+            f,
+            " at column {}\n{}\n{}{}",
+            self.column,
+            self.pattern,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width.max(1))
+        )
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+#[derive(Clone)]
+enum Node {
+    Attr(Arc<RegisteredAttr>),
+    /// Lowercase
+    Text(String),
+    /// Any token but whitespace
+    Any,
+    Seq(Vec<Node>),
+    AnyOf(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+    },
+    Capture {
+        capture_idx: usize,
+        node: Box<Node>,
+    },
+}
+
+impl fmt::Debug for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Node::Attr(attr) => write!(f, "[{}]", attr.name),
+            Node::Text(text) => write!(f, "{:?}", text),
+            Node::Any => write!(f, "_"),
+            Node::Seq(nodes) => f.debug_tuple("Seq").field(nodes).finish(),
+            Node::AnyOf(nodes) => f.debug_tuple("AnyOf").field(nodes).finish(),
+            Node::Repeat { node, min, max } => f
+                .debug_struct("Repeat")
+                .field("node", node)
+                .field("min", min)
+                .field("max", max)
+                .finish(),
+            Node::Capture { capture_idx, node } => f
+                .debug_struct("Capture")
+                .field("capture_idx", capture_idx)
+                .field("node", node)
+                .finish(),
+        }
+    }
+}
+
+/// Matcher parsed from text, referring to attribute types by name through a [PatternRegistry].
+///
+/// | Syntax         | Matches                                                        |
+/// |----------------|----------------------------------------------------------------|
+/// | `[Name]`       | an attribute registered as `Name`, like `[WORD]`               |
+/// | `"text"`       | a token with this text, ignoring case (`\"` and `\\` escapes) |
+/// | `_`            | any token but whitespace                                       |
+/// | `a b`          | `a` then `b`, with any whitespace between them                 |
+/// | `a \| b`       | `a` or `b`                                                     |
+/// | `( ... )`      | a group                                                        |
+/// | `a?` `a*` `a+` | `a` optionally, any number of times, at least once             |
+/// | `name:a`       | `a`, captured as `name`, see [PatternCaptures]                 |
+///
+/// ```
+/// use layered_nlp::{create_line_from_string, x, LLSelection};
+///
+/// let pattern = x::Pattern::parse(r#""as" "soon" "as" [WORD]"#, &Default::default()).unwrap();
+/// let ll_line = create_line_from_string("Call me as soon as possible.");
+/// let selection = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+/// assert_eq!(selection.find_by(&pattern).len(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    node: Node,
+    /// Names of the `name:` captures, indexed by [Node::Capture]
+    capture_names: Vec<Arc<str>>,
+}
+
+/// A `name:` capture of a [Pattern], like [Captured](super::Captured) with a name shared with the pattern
+#[derive(Clone, Debug, PartialEq)]
+pub struct PatternCapture {
+    pub name: Arc<str>,
+    pub spanned: Spanned<()>,
+}
+
+/// Output of a [Pattern], with the `name:` captures which matched, in pattern order.
+///
+/// Use [LLSelection::find_captures](crate::LLSelection::find_captures) to look them up by name.
+pub type PatternCaptures = Vec<PatternCapture>;
+
+impl Pattern {
+    pub fn parse(pattern: &str, registry: &PatternRegistry) -> Result<Pattern, PatternError> {
+        let mut parser = Parser {
+            pattern,
+            chars: pattern.chars().collect(),
+            pos: 0,
+            registry,
+            capture_names: Vec::new(),
+        };
+        let node = parser.any_of()?;
+        match parser.peek() {
+            None => Ok(Pattern {
+                node,
+                capture_names: parser.capture_names,
+            }),
+            Some(')') => Err(parser.error(
+                PatternErrorKind::UnexpectedChar {
+                    found: ')',
+                    expected: "a pattern element",
+                },
+                parser.pos,
+                1,
+            )),
+            Some(_) => unreachable!("elements are parsed until `)` or the end"),
+        }
+    }
+}
+
+impl<'l> XMatch<'l> for Pattern {
+    type Out = PatternCaptures;
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        let mut found = Vec::new();
+        for path in self.node.go(direction, ll_line) {
+            let mut spans = vec![None; self.capture_names.len()];
+            for (capture_idx, span) in path.captures {
+                // the first capture of a name wins, like in [Captures](super::Captures)
+                spans[capture_idx].get_or_insert(span);
+            }
+            let captures: PatternCaptures = spans
+                .into_iter()
+                .enumerate()
+                .filter_map(|(capture_idx, span)| {
+                    Some(PatternCapture {
+                        name: self.capture_names[capture_idx].clone(),
+                        spanned: Spanned {
+                            value: (),
+                            span: Some(span?),
+                        },
+                    })
+                })
+                .collect();
+            let found_match = (captures, path.to_idx);
+            if !found.contains(&found_match) {
+                found.push(found_match);
+            }
+        }
+        found
+    }
+}
+
+/// A way to match a [Node]
+#[derive(Clone, PartialEq)]
+struct Path {
+    /// [XDirection::zero_width] when matching nothing
+    to_idx: ToIdx,
+    /// Capture index and tokens
+    captures: Vec<(usize, LRange)>,
+}
+
+impl Path {
+    fn new(to_idx: ToIdx) -> Self {
+        Path {
+            to_idx,
+            captures: Vec::new(),
+        }
+    }
+}
+
+fn push_distinct(paths: &mut Vec<Path>, path: Path) {
+    if !paths.contains(&path) {
+        paths.push(path);
+    }
+}
+
+impl Node {
+    fn go<'l, M: XDirection<'l>>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<Path> {
+        match self {
+            Node::Attr(attr) => direction
+                .attr_ranges(attr.type_id, ll_line)
+                .into_iter()
+                .filter(|(range, _)| (attr.matches)(ll_line, *range))
+                .map(|(_, to_idx)| Path::new(to_idx))
+                .collect(),
+            Node::Text(text) => direction
+                .text_token(ll_line)
+                .filter(|(token_text, _)| token_text.to_lowercase() == *text)
+                .map(|(_, to_idx)| Path::new(to_idx))
+                .into_iter()
+                .collect(),
+            Node::Any => {
+                if !whitespace().go(direction, ll_line).is_empty() {
+                    return Vec::new();
+                }
+                direction
+                    .text_token(ll_line)
+                    .map(|(_, to_idx)| to_idx)
+                    .or_else(|| direction.value_token(ll_line).map(|(_, to_idx)| to_idx))
+                    .map(Path::new)
+                    .into_iter()
+                    .collect()
+            }
+            Node::AnyOf(nodes) => {
+                let mut paths = Vec::new();
+                for node in nodes {
+                    for path in node.go(direction, ll_line) {
+                        push_distinct(&mut paths, path);
+                    }
+                }
+                paths
+            }
+            Node::Seq(nodes) => {
+                let mut paths = vec![Path::new(direction.zero_width())];
+                for node in nodes {
+                    paths = node.go_after(direction, &paths, ll_line);
+                }
+                paths
+            }
+            Node::Repeat { node, min, max } => {
+                let mut paths = Vec::new();
+                if *min == 0 {
+                    paths.push(Path::new(direction.zero_width()));
+                }
+                let mut seen = vec![direction.zero_width()];
+                let mut last = vec![Path::new(direction.zero_width())];
+                let mut count = 0;
+                while !last.is_empty() && max.is_none_or(|max| count < max) {
+                    count += 1;
+                    last = node
+                        .go_after(direction, &last, ll_line)
+                        .into_iter()
+                        .filter(|path| !seen.contains(&path.to_idx))
+                        .collect();
+                    seen.extend(last.iter().map(|path| path.to_idx));
+                    if count >= *min {
+                        for path in &last {
+                            push_distinct(&mut paths, path.clone());
+                        }
+                    }
+                }
+                paths
+            }
+            Node::Capture { capture_idx, node } => node
+                .go(direction, ll_line)
+                .into_iter()
+                .map(|mut path| {
+                    if let Some(span) = direction.span_to(path.to_idx) {
+                        path.captures.push((*capture_idx, span));
+                    }
+                    path
+                })
+                .collect(),
+        }
+    }
+
+    /// Match after each of `paths` with any whitespace in between, keeping the previous end
+    /// when matching nothing
+    fn go_after<'l, M: XDirection<'l>>(
+        &self,
+        direction: &M,
+        paths: &[Path],
+        ll_line: &'l LLLine,
+    ) -> Vec<Path> {
+        let mut next_paths = Vec::new();
+        for path in paths {
+            // no whitespace before the first element
+            let directions = if path.to_idx == direction.zero_width() {
+                direction
+                    .after(path.to_idx.0, ll_line)
+                    .into_iter()
+                    .collect()
+            } else {
+                after_skipping(direction, path.to_idx.0, &whitespace(), ll_line)
+            };
+            for next in directions {
+                for next_path in self.go(&next, ll_line) {
+                    let to_idx = if next_path.to_idx == next.zero_width() {
+                        path.to_idx
+                    } else {
+                        next_path.to_idx
+                    };
+                    let mut captures = path.captures.clone();
+                    captures.extend(next_path.captures);
+                    push_distinct(&mut next_paths, Path { to_idx, captures });
+                }
+            }
+        }
+        next_paths
+    }
+}
+
+struct Parser<'a> {
+    pattern: &'a str,
+    chars: Vec<char>,
+    /// Index in `chars`
+    pos: usize,
+    registry: &'a PatternRegistry,
+    capture_names: Vec<Arc<str>>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, kind: PatternErrorKind, pos: usize, width: usize) -> PatternError {
+        PatternError {
+            kind,
+            column: pos + 1,
+            width,
+            pattern: self.pattern.to_string(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.get(self.pos).is_some_and(|c| c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Next char which isn't whitespace
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.get(self.pos).copied()
+    }
+
+    /// `seq | seq | ...`
+    fn any_of(&mut self) -> Result<Node, PatternError> {
+        let mut nodes = vec![self.seq()?];
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            nodes.push(self.seq()?);
+        }
+        Ok(if nodes.len() == 1 {
+            nodes.remove(0)
+        } else {
+            Node::AnyOf(nodes)
+        })
+    }
+
+    /// `item item ...`, until `|`, `)` or the end
+    fn seq(&mut self) -> Result<Node, PatternError> {
+        let mut nodes = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some('|') | Some(')') => break,
+                Some(c @ '?') | Some(c @ '*') | Some(c @ '+') => {
+                    return Err(self.error(PatternErrorKind::NothingToRepeat(c), self.pos, 1))
+                }
+                Some(_) => nodes.push(self.item()?),
+            }
+        }
+        match nodes.len() {
+            0 => Err(self.error(PatternErrorKind::Empty, self.pos, 1)),
+            1 => Ok(nodes.remove(0)),
+            _ => Ok(Node::Seq(nodes)),
+        }
+    }
+
+    /// Element, optionally followed by `?`, `*` or `+`, and optionally captured with `name:`
+    fn item(&mut self) -> Result<Node, PatternError> {
+        let start = self.pos;
+        if !self.chars[start].is_alphabetic() {
+            return self.repeated_element();
+        }
+
+        let name_len = self.chars[start..]
+            .iter()
+            .take_while(|c| c.is_alphanumeric() || **c == '_' || **c == '-')
+            .count();
+        self.pos += name_len;
+        match self.chars.get(self.pos) {
+            Some(':') => self.pos += 1,
+            Some(&found) => {
+                return Err(self.error(
+                    PatternErrorKind::UnexpectedChar {
+                        found,
+                        expected: "`:` after the capture name",
+                    },
+                    self.pos,
+                    1,
+                ))
+            }
+            None => {
+                return Err(self.error(
+                    PatternErrorKind::UnexpectedEnd {
+                        expected: "`:` after the capture name",
+                    },
+                    start,
+                    name_len,
+                ))
+            }
+        }
+        if self.peek().is_none() {
+            return Err(self.error(
+                PatternErrorKind::UnexpectedEnd {
+                    expected: "an element to capture",
+                },
+                start,
+                name_len + 1,
+            ));
+        }
+
+        let name: String = self.chars[start..start + name_len].iter().collect();
+        let capture_idx = match self.capture_names.iter().position(|other| **other == name) {
+            Some(capture_idx) => capture_idx,
+            None => {
+                self.capture_names.push(name.into());
+                self.capture_names.len() - 1
+            }
+        };
+        Ok(Node::Capture {
+            capture_idx,
+            node: Box::new(self.repeated_element()?),
+        })
+    }
+
+    fn repeated_element(&mut self) -> Result<Node, PatternError> {
+        let node = self.element()?;
+        let (min, max) = match self.chars.get(self.pos) {
+            Some('?') => (0, Some(1)),
+            Some('*') => (0, None),
+            Some('+') => (1, None),
+            _ => return Ok(node),
+        };
+        self.pos += 1;
+        Ok(Node::Repeat {
+            node: Box::new(node),
+            min,
+            max,
+        })
+    }
+
+    fn element(&mut self) -> Result<Node, PatternError> {
+        let start = self.pos;
+        match self.chars[start] {
+            '[' => {
+                let end = self.find_closing(start, ']', "`]`")?;
+                let name: String = self.chars[start + 1..end].iter().collect();
+                let name = name.trim();
+                self.pos = end + 1;
+                match self.registry.attrs.get(name) {
+                    Some(attr) => Ok(Node::Attr(attr.clone())),
+                    None if name.is_empty() => Err(self.error(
+                        PatternErrorKind::UnexpectedChar {
+                            found: ']',
+                            expected: "an attribute name",
+                        },
+                        end,
+                        1,
+                    )),
+                    None => Err(self.error(
+                        PatternErrorKind::UnknownAttribute(name.to_string()),
+                        start,
+                        end + 1 - start,
+                    )),
+                }
+            }
+            '"' => {
+                let mut text = String::new();
+                let mut pos = start + 1;
+                loop {
+                    match self.chars.get(pos) {
+                        None => {
+                            return Err(self.error(
+                                PatternErrorKind::UnexpectedEnd {
+                                    expected: "a closing `\"`",
+                                },
+                                start,
+                                1,
+                            ))
+                        }
+                        Some('"') => break,
+                        Some('\\') if matches!(self.chars.get(pos + 1), Some('"' | '\\')) => {
+                            text.push(self.chars[pos + 1]);
+                            pos += 2;
+                        }
+                        Some(c) if c.is_whitespace() => {
+                            return Err(self.error(PatternErrorKind::WhitespaceInText, pos, 1))
+                        }
+                        Some(c) => {
+                            text.push(*c);
+                            pos += 1;
+                        }
+                    }
+                }
+                self.pos = pos + 1;
+                if text.is_empty() {
+                    return Err(self.error(PatternErrorKind::Empty, start, 2));
+                }
+                Ok(Node::Text(text.to_lowercase()))
+            }
+            '_' => {
+                self.pos += 1;
+                Ok(Node::Any)
+            }
+            '(' => {
+                self.pos += 1;
+                let node = self.any_of()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.pos += 1;
+                        Ok(node)
+                    }
+                    _ => Err(self.error(
+                        PatternErrorKind::UnexpectedEnd {
+                            expected: "a closing `)`",
+                        },
+                        start,
+                        1,
+                    )),
+                }
+            }
+            found => Err(self.error(
+                PatternErrorKind::UnexpectedChar {
+                    found,
+                    expected: "`[`, `\"`, `_`, `(` or a capture name",
+                },
+                start,
+                1,
+            )),
+        }
+    }
+
+    /// Index of the `closing` char after `start`
+    fn find_closing(
+        &self,
+        start: usize,
+        closing: char,
+        expected: &'static str,
+    ) -> Result<usize, PatternError> {
+        self.chars[start..]
+            .iter()
+            .position(|c| *c == closing)
+            .map(|offset| start + offset)
+            .ok_or_else(|| self.error(PatternErrorKind::UnexpectedEnd { expected }, start, 1))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{FinishWith, LLSelection};
+
+    fn parse_error(pattern: &str) -> String {
+        let registry = PatternRegistry::default();
+        Pattern::parse(pattern, &registry).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn errors() {
+        insta::assert_snapshot!(parse_error(r#"[WORD] _? [Amonut]"#), @r###"
+        Unknown attribute `Amonut` at column 11
+        [WORD] _? [Amonut]
+                  ^^^^^^^^
+        "###);
+        insta::assert_snapshot!(parse_error(r#""as" "soon as""#), @r###"
+        Text matches a single token and can't contain whitespace, quote each word instead at column 11
+        "as" "soon as"
+                  ^
+        "###);
+        insta::assert_snapshot!(parse_error(r#"[WORD] ("as" | )"#), @r###"
+        Empty pattern at column 16
+        [WORD] ("as" | )
+                       ^
+        "###);
+        insta::assert_snapshot!(parse_error(r#"[WORD"#), @r###"
+        Pattern ended, expected `]` at column 1
+        [WORD
+        ^
+        "###);
+        insta::assert_snapshot!(parse_error(r#"[WORD] * "as""#), @r###"
+        Nothing to repeat before `*` at column 8
+        [WORD] * "as"
+               ^
+        "###);
+        insta::assert_snapshot!(parse_error(r#"[WORD] ) "as""#), @r###"
+        Unexpected `)`, expected a pattern element at column 8
+        [WORD] ) "as"
+               ^
+        "###);
+        insta::assert_snapshot!(parse_error(r#"[WORD] amount [NATN]"#), @r###"
+        Unexpected ` `, expected `:` after the capture name at column 14
+        [WORD] amount [NATN]
+                     ^
+        "###);
+        insta::assert_snapshot!(parse_error(r#"[WORD] & "as""#), @r###"
+        Unexpected `&`, expected `[`, `"`, `_`, `(` or a capture name at column 8
+        [WORD] & "as"
+               ^
+        "###);
+    }
+
+    #[test]
+    fn matches() {
+        let find = |pattern: &'static str| {
+            crate::tests::test_resolver(
+                "Pay $ 1,000 or $20 as soon as you can, please!",
+                |selection| {
+                    let registry = PatternRegistry::default();
+                    let matcher = Pattern::parse(pattern, &registry).unwrap();
+                    selection
+                        .find_by(&matcher)
+                        .finish_with(|_| pattern.to_string())
+                },
+            )
+        };
+
+        insta::assert_snapshot!(find(r#"[SYMB] _? [NATN]"#), @r###"
+        Pay     $     1  ,  000     or     $  20     as     soon     as     you     can  ,     please  !
+                ╰─────╯"[SYMB] _? [NATN]"
+                                           ╰───╯"[SYMB] _? [NATN]"
+        "###);
+        insta::assert_snapshot!(find(r#""AS" "soon" "as""#), @r###"
+        Pay     $     1  ,  000     or     $  20     as     soon     as     you     can  ,     please  !
+                                                     ╰────────────────╯"\"AS\" \"soon\" \"as\""
+        "###);
+        insta::assert_snapshot!(find(r#"[NATN] ([PUNC] [NATN])*"#), @r###"
+        Pay     $     1  ,  000     or     $  20     as     soon     as     you     can  ,     please  !
+                      ╰"[NATN] ([PUNC] [NATN])*"
+                      ╰───────╯"[NATN] ([PUNC] [NATN])*"
+                            ╰─╯"[NATN] ([PUNC] [NATN])*"
+                                              ╰╯"[NATN] ([PUNC] [NATN])*"
+        "###);
+        insta::assert_snapshot!(find(r#"("or" | "as") [WORD]+ [PUNC]"#), @r###"
+        Pay     $     1  ,  000     or     $  20     as     soon     as     you     can  ,     please  !
+                                                     ╰───────────────────────────────────╯"(\"or\" | \"as\") [WORD]+ [PUNC]"
+                                                                     ╰───────────────────╯"(\"or\" | \"as\") [WORD]+ [PUNC]"
+        "###);
+    }
+
+    #[test]
+    fn captures() {
+        let registry = PatternRegistry::default();
+        let pattern = Pattern::parse(
+            r#"verb:[WORD] ( symbol:[SYMB] | "$" ) amount:[NATN]+"#,
+            &registry,
+        )
+        .unwrap();
+        let ll_line = crate::tests::test_line("Pay $ 100, or pay €20");
+        let selection = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+
+        let found: Vec<String> = selection
+            .find_captures(&pattern)
+            .into_iter()
+            .map(|(sel, captures)| {
+                let captures: Vec<String> = captures
+                    .names()
+                    .map(|name| {
+                        let sub_sel = captures.selection(name).unwrap();
+                        format!("{}: {}..={}", name, sub_sel.start_idx, sub_sel.end_idx)
+                    })
+                    .collect();
+                format!(
+                    "{}..={} {}",
+                    sel.start_idx,
+                    sel.end_idx,
+                    captures.join(", ")
+                )
+            })
+            .collect();
+        insta::assert_debug_snapshot!(found, @r###"
+        [
+            "0..=4 verb: 0..=0, symbol: 2..=2, amount: 4..=4",
+            "0..=4 verb: 0..=0, amount: 4..=4",
+            "9..=12 verb: 9..=9, symbol: 11..=11, amount: 12..=12",
+        ]
+        "###);
+    }
+
+    #[test]
+    fn many_captures() {
+        let pattern = Pattern::parse(
+            "a:_ b:_ c:_ d:_ e:_ f:_ g:_ h:_ i:_ j:_",
+            &PatternRegistry::default(),
+        )
+        .unwrap();
+        let ll_line = crate::tests::test_line("0 1 2 3 4 5 6 7 8 9");
+        let selection = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+
+        let found = selection.find_captures(&pattern);
+        assert_eq!(found.len(), 1);
+        let (_, captures) = &found[0];
+        assert_eq!(captures.names().collect::<String>(), "abcdefghij");
+        assert_eq!(captures.selection("j").unwrap().start_idx, 18);
+    }
+}
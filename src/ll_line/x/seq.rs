@@ -40,7 +40,7 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>> XMatch<'l> for Seq2<A, B> {
                     .map(|direction| self.1.go(&direction, ll_line))
                     .unwrap_or_else(Vec::new)
                     .into_iter()
-                    .map(move |(b, to_idx)| ((a, b), to_idx))
+                    .map(move |(b, to_idx)| ((a.clone(), b), to_idx))
             })
             .collect()
     }
@@ -65,12 +65,13 @@ impl<'l, A: XMatch<'l>, B: XMatch<'l>, C: XMatch<'l>> XMatch<'l> for Seq3<A, B,
                     .unwrap_or_else(Vec::new)
                     .into_iter()
                     .flat_map(move |(b, to_idx)| {
+                        let a = a.clone();
                         direction
                             .after(to_idx.0, ll_line)
                             .map(|direction| self.2.go(&direction, ll_line))
                             .unwrap_or_else(Vec::new)
                             .into_iter()
-                            .map(move |(c, to_idx)| ((a, b, c), to_idx))
+                            .map(move |(c, to_idx)| ((a.clone(), b.clone(), c), to_idx))
                     })
             })
             .collect()
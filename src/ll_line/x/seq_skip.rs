@@ -0,0 +1,161 @@
+use std::marker::PhantomData;
+
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+pub trait SeqSkip<S> {
+    type Out;
+
+    fn into_seq_skip(self, skip: S) -> Self::Out;
+}
+
+impl<A, B, S> SeqSkip<S> for (A, B) {
+    type Out = SeqSkip2<A, B, S>;
+
+    fn into_seq_skip(self, skip: S) -> Self::Out {
+        SeqSkip2(self.0, self.1, skip)
+    }
+}
+
+impl<A, B, C, S> SeqSkip<S> for (A, B, C) {
+    type Out = SeqSkip3<A, B, C, S>;
+
+    fn into_seq_skip(self, skip: S) -> Self::Out {
+        SeqSkip3(self.0, self.1, self.2, skip)
+    }
+}
+
+/// Wraps each element of a tuple in [NotOnly], for [seq_ignoring_stopwords](super::seq_ignoring_stopwords)
+pub trait NotOnlyEach<A> {
+    type Out;
+
+    fn not_only_each(self) -> Self::Out;
+}
+
+impl<M1, M2, A> NotOnlyEach<A> for (M1, M2) {
+    type Out = (NotOnly<M1, A>, NotOnly<M2, A>);
+
+    fn not_only_each(self) -> Self::Out {
+        (NotOnly(self.0, PhantomData), NotOnly(self.1, PhantomData))
+    }
+}
+
+impl<M1, M2, M3, A> NotOnlyEach<A> for (M1, M2, M3) {
+    type Out = (NotOnly<M1, A>, NotOnly<M2, A>, NotOnly<M3, A>);
+
+    fn not_only_each(self) -> Self::Out {
+        (
+            NotOnly(self.0, PhantomData),
+            NotOnly(self.1, PhantomData),
+            NotOnly(self.2, PhantomData),
+        )
+    }
+}
+
+/// Matches of the matcher which don't only cover tokens with `A` attributes
+pub struct NotOnly<M, A>(pub M, PhantomData<fn() -> A>);
+
+impl<'l, M: XMatch<'l>, A: 'static> XMatch<'l> for NotOnly<M, A> {
+    type Out = M::Out;
+
+    fn go<D>(&self, direction: &D, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        D: XDirection<'l>,
+    {
+        self.0
+            .go(direction, ll_line)
+            .into_iter()
+            .filter(|(_, to_idx)| match direction.span_to(*to_idx) {
+                Some((start_idx, end_idx)) => !(start_idx..=end_idx).all(|idx| {
+                    ll_line.attrs.starts_at[idx]
+                        .get::<A>()
+                        .contains(&(idx, idx))
+                }),
+                None => true,
+            })
+            .collect()
+    }
+}
+
+/// Directions to continue from after `idx`, directly or after any number of `skip` matches
+pub(super) fn after_skipping<'l, M, S>(
+    direction: &M,
+    idx: usize,
+    skip: &S,
+    ll_line: &'l LLLine,
+) -> Vec<M>
+where
+    M: XDirection<'l>,
+    S: XMatch<'l>,
+{
+    let mut visited = vec![idx];
+    let mut directions = Vec::new();
+    let mut next = 0;
+
+    while let Some(&idx) = visited.get(next) {
+        next += 1;
+        if let Some(direction) = direction.after(idx, ll_line) {
+            for (_, ToIdx(skipped_idx)) in skip.go(&direction, ll_line) {
+                if !visited.contains(&skipped_idx) {
+                    visited.push(skipped_idx);
+                }
+            }
+            directions.push(direction);
+        }
+    }
+
+    directions
+}
+
+/// Like [Seq2](super::Seq2), allowing tokens matched by the third matcher between the elements
+pub struct SeqSkip2<A, B, S>(pub A, pub B, pub S);
+
+impl<'l, A: XMatch<'l>, B: XMatch<'l>, S: XMatch<'l>> XMatch<'l> for SeqSkip2<A, B, S> {
+    type Out = (A::Out, B::Out);
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        self.0
+            .go(direction, ll_line)
+            .into_iter()
+            .flat_map(|(a, to_idx)| {
+                after_skipping(direction, to_idx.0, &self.2, ll_line)
+                    .into_iter()
+                    .flat_map(|direction| self.1.go(&direction, ll_line))
+                    .map(move |(b, to_idx)| ((a.clone(), b), to_idx))
+            })
+            .collect()
+    }
+}
+
+/// Like [Seq3](super::Seq3), allowing tokens matched by the fourth matcher between the elements
+pub struct SeqSkip3<A, B, C, S>(pub A, pub B, pub C, pub S);
+
+impl<'l, A: XMatch<'l>, B: XMatch<'l>, C: XMatch<'l>, S: XMatch<'l>> XMatch<'l>
+    for SeqSkip3<A, B, C, S>
+{
+    type Out = (A::Out, B::Out, C::Out);
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        self.0
+            .go(direction, ll_line)
+            .into_iter()
+            .flat_map(|(a, to_idx)| {
+                after_skipping(direction, to_idx.0, &self.3, ll_line)
+                    .into_iter()
+                    .flat_map(|direction| self.1.go(&direction, ll_line))
+                    .flat_map(move |(b, to_idx)| {
+                        let a = a.clone();
+                        after_skipping(direction, to_idx.0, &self.3, ll_line)
+                            .into_iter()
+                            .flat_map(move |direction| self.2.go(&direction, ll_line))
+                            .map(move |(c, to_idx)| ((a.clone(), b.clone(), c), to_idx))
+                    })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,77 @@
+use super::{Captures, CollectCaptures, LLLine, Seq2, Seq3, ToIdx, XDirection, XMatch};
+use crate::LLSelection;
+
+pub trait SeqSpans {
+    type Out;
+
+    fn into_seq_spans(self) -> Self::Out;
+}
+
+impl<A, B> SeqSpans for (A, B) {
+    type Out = Seq2<SpannedMatcher<A>, SpannedMatcher<B>>;
+
+    fn into_seq_spans(self) -> Self::Out {
+        Seq2(SpannedMatcher(self.0), SpannedMatcher(self.1))
+    }
+}
+
+impl<A, B, C> SeqSpans for (A, B, C) {
+    type Out = Seq3<SpannedMatcher<A>, SpannedMatcher<B>, SpannedMatcher<C>>;
+
+    fn into_seq_spans(self) -> Self::Out {
+        Seq3(
+            SpannedMatcher(self.0),
+            SpannedMatcher(self.1),
+            SpannedMatcher(self.2),
+        )
+    }
+}
+
+pub struct SpannedMatcher<M>(pub M);
+
+/// Output of a matcher with the tokens it took, see [spanned](super::spanned)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spanned<V> {
+    pub value: V,
+    /// Tokens taken by the match (start, end inclusive), `None` for zero-width matches
+    pub(super) span: Option<(usize, usize)>,
+}
+
+impl<V> Spanned<V> {
+    /// Tokens taken by the match, in the line of `selection`. `None` for zero-width matches.
+    pub fn selection(&self, selection: &LLSelection) -> Option<LLSelection> {
+        let (start_idx, end_idx) = self.span?;
+        Some(LLSelection {
+            ll_line: selection.ll_line.clone(),
+            start_idx,
+            end_idx,
+        })
+    }
+}
+
+impl<'l, M: XMatch<'l>> XMatch<'l> for SpannedMatcher<M> {
+    type Out = Spanned<M::Out>;
+
+    fn go<D>(&self, direction: &D, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        D: XDirection<'l>,
+    {
+        self.0
+            .go(direction, ll_line)
+            .into_iter()
+            .map(|(value, to_idx)| {
+                let spanned = Spanned {
+                    value,
+                    span: direction.span_to(to_idx),
+                };
+                (spanned, to_idx)
+            })
+            .collect()
+    }
+}
+
+impl<'l, V: CollectCaptures<'l>> CollectCaptures<'l> for Spanned<V> {
+    fn collect_captures(&self, captures: &mut Captures<'l>) {
+        self.value.collect_captures(captures);
+    }
+}
@@ -0,0 +1,110 @@
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+/// Never matches, the default for [Then::not_crossing]
+pub struct Nothing;
+
+impl<'l> XMatch<'l> for Nothing {
+    type Out = ();
+
+    fn go<M>(&self, _: &M, _: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        Vec::new()
+    }
+}
+
+/// Match `a`, then `b` after any number of tokens, see [then_within](super::then_within)
+pub struct Then<A, B, X = Nothing> {
+    a: A,
+    b: B,
+    max_gap: Option<usize>,
+    not_crossing: X,
+}
+
+impl<A, B> Then<A, B> {
+    pub(crate) fn new(a: A, b: B, max_gap: Option<usize>) -> Self {
+        Then {
+            a,
+            b,
+            max_gap,
+            not_crossing: Nothing,
+        }
+    }
+}
+
+impl<A, B, X> Then<A, B, X> {
+    /// Don't skip over tokens where `matcher` matches, like `x::attr_eq(&TextTag::PUNC)`
+    pub fn not_crossing<Y>(self, matcher: Y) -> Then<A, B, Y> {
+        Then {
+            a: self.a,
+            b: self.b,
+            max_gap: self.max_gap,
+            not_crossing: matcher,
+        }
+    }
+}
+
+impl<'l, A: XMatch<'l>, B: XMatch<'l>, X: XMatch<'l>> XMatch<'l> for Then<A, B, X> {
+    type Out = (A::Out, B::Out);
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        let mut matches = Vec::new();
+        // matches starting in the selection stay in it, matches starting next to it
+        // (see `LLSelection::match_forwards`) can go on until the end of the line
+        let (selection_start, selection_end) = direction.selection();
+        let in_selection = |idx: usize| selection_start <= idx && idx <= selection_end;
+        let bounded = next_token_idx(direction, ll_line).is_some_and(in_selection);
+        let in_bounds = |idx: usize| !bounded || in_selection(idx);
+
+        for (a, to_idx) in self.a.go(direction, ll_line) {
+            let mut gap = 0;
+            let mut next = direction.after(to_idx.0, ll_line);
+
+            while let Some(direction) = next {
+                matches.extend(
+                    self.b
+                        .go(&direction, ll_line)
+                        .into_iter()
+                        // `b` can't take tokens past the selection
+                        .filter(|(_, to_idx)| match direction.span_to(*to_idx) {
+                            Some((start_idx, end_idx)) => {
+                                in_bounds(start_idx) && in_bounds(end_idx)
+                            }
+                            None => true,
+                        })
+                        .map(|(b, to_idx)| ((a.clone(), b), to_idx)),
+                );
+
+                if self.max_gap.is_some_and(|max_gap| gap >= max_gap)
+                    || !self.not_crossing.go(&direction, ll_line).is_empty()
+                {
+                    break;
+                }
+
+                // skip the next token, if any
+                let token_idx = match next_token_idx(&direction, ll_line) {
+                    Some(token_idx) if in_bounds(token_idx) => token_idx,
+                    _ => break,
+                };
+                gap += 1;
+                next = direction.after(token_idx, ll_line);
+            }
+        }
+
+        matches
+    }
+}
+
+/// Index of the token `direction` continues with, if any
+fn next_token_idx<'l, M: XDirection<'l>>(direction: &M, ll_line: &'l LLLine) -> Option<usize> {
+    match direction.text_token(ll_line) {
+        Some((_, token_idx)) => Some(token_idx.0),
+        None => direction
+            .value_token(ll_line)
+            .map(|(_, token_idx)| token_idx.0),
+    }
+}
@@ -0,0 +1,20 @@
+use std::marker::PhantomData;
+
+use super::{LLLine, ToIdx, XDirection, XMatch};
+
+pub struct Value<T>(pub(crate) PhantomData<T>);
+
+impl<'l, T: 'static> XMatch<'l> for Value<T> {
+    type Out = &'l T;
+
+    fn go<M>(&self, direction: &M, ll_line: &'l LLLine) -> Vec<(Self::Out, ToIdx)>
+    where
+        M: XDirection<'l>,
+    {
+        direction
+            .value_token(ll_line)
+            .and_then(|(value, to_idx)| value.payload::<T>().map(|payload| (payload, to_idx)))
+            .into_iter()
+            .collect()
+    }
+}
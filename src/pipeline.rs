@@ -0,0 +1,103 @@
+use std::any::TypeId;
+
+use crate::ll_line::LRange;
+use crate::type_bucket::AnyAttribute;
+use crate::{LLLine, LLSelection, Resolver};
+
+type ResolveFn = Box<dyn Fn(LLSelection) -> Vec<(LRange, AnyAttribute)>>;
+
+/// An ordered list of [Resolver]s, which can be run on a whole line or, through a
+/// [LineEditor](crate::LineEditor), to only assign attributes again around an edit.
+///
+/// ```
+/// use layered_nlp::{create_line_from_string, ContractionResolver, Pipeline};
+///
+/// let pipeline = Pipeline::new().with(ContractionResolver);
+/// let ll_line = pipeline.run(create_line_from_string("I don't know"));
+/// ```
+#[derive(Default)]
+pub struct Pipeline {
+    resolvers: Vec<(TypeId, ResolveFn)>,
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Pipeline::default()
+    }
+
+    /// Add a resolver running after the resolvers already added.
+    pub fn with<R: Resolver + 'static>(mut self, resolver: R) -> Self {
+        self.resolvers.push((
+            TypeId::of::<R::Attr>(),
+            Box::new(move |selection| {
+                resolver
+                    .go(selection)
+                    .into_iter()
+                    .map(|assignment| assignment.into_any())
+                    .collect()
+            }),
+        ));
+        self
+    }
+
+    /// Run all resolvers on the whole line, same as calling [LLLine::run] with each of them.
+    pub fn run(&self, ll_line: LLLine) -> LLLine {
+        match ll_line.ll_tokens().len() {
+            // Empty line can't recognize anything since they can't create `LLSelection`
+            0 => ll_line,
+            token_count => self
+                .resolvers
+                .iter()
+                .fold(ll_line, |ll_line, (_, resolve)| {
+                    ll_line.run_any_within((0, token_count - 1), resolve)
+                }),
+        }
+    }
+
+    /// Assign the attributes of all resolvers overlapping the tokens of `range` again.
+    ///
+    /// Only removing and assigning attributes is incremental: every resolver still runs over the
+    /// whole line, on each round, and assignments not overlapping `range` are dropped. `range`
+    /// grows to cover the attributes which are removed or assigned, so that later resolvers can
+    /// build on them, until a round doesn't grow it. The line then matches a run from scratch as
+    /// long as resolvers only look at the tokens their attributes cover. Attributes assigned
+    /// from tokens outside of them, like a word marked for what follows it, aren't updated.
+    pub(crate) fn run_within(&self, mut ll_line: LLLine, mut range: LRange) -> LLLine {
+        let whole_line = (0, ll_line.ll_tokens().len() - 1);
+        let cover = |range: LRange, (start_idx, end_idx): LRange| {
+            (range.0.min(start_idx), range.1.max(end_idx))
+        };
+
+        loop {
+            let mut covered = ll_line
+                .remove_attrs_overlapping(&self.attr_type_ids(), range)
+                .into_iter()
+                .fold(range, cover);
+
+            for (_, resolve) in &self.resolvers {
+                ll_line = ll_line.run_any_within(whole_line, |selection| {
+                    let assignments: Vec<_> = resolve(selection)
+                        .into_iter()
+                        .filter(|((start_idx, end_idx), _)| {
+                            *start_idx <= range.1 && *end_idx >= range.0
+                        })
+                        .collect();
+                    covered = assignments
+                        .iter()
+                        .fold(covered, |covered, (assigned, _)| cover(covered, *assigned));
+                    assignments
+                });
+            }
+
+            if covered == range {
+                return ll_line;
+            }
+            range = covered;
+        }
+    }
+
+    /// Types of the attributes assigned by the resolvers
+    pub(crate) fn attr_type_ids(&self) -> Vec<TypeId> {
+        self.resolvers.iter().map(|(type_id, _)| *type_id).collect()
+    }
+}
@@ -1,3 +1,13 @@
+mod contraction;
+mod fuzzy_match;
+mod normalize;
+mod sentence;
+mod stopword;
 mod text_match;
 
+pub use contraction::{Contraction, ContractionResolver};
+pub use fuzzy_match::{FuzzyMatch, FuzzyTextMatchAssignResolver};
+pub use normalize::{NormalizeResolver, Normalized};
+pub use sentence::{Sentence, SentenceResolver};
+pub use stopword::{Stopword, StopwordLanguage, StopwordResolver};
 pub use text_match::TextMatchAssignResolver;
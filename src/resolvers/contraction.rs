@@ -0,0 +1,158 @@
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// The parts of a contracted [TextTag::WORD] token like `don't` or `we’ll`.
+///
+/// `base` and `clitic` are the original text of the token, so `base + clitic` is always the token text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contraction {
+    /// `do` for `don't`, `ca` for `can't`, `we` for `we'll`
+    pub base: String,
+    /// `n't` for `don't`, `'ll` for `we'll`
+    pub clitic: String,
+}
+
+impl Contraction {
+    /// The word the base stands for, which differs from [Contraction::base] for
+    /// irregular negations like `can't` (`can`), `won't` (`will`) and `shan't` (`shall`).
+    pub fn expanded_base(&self) -> &str {
+        if !self.is_negation() {
+            return &self.base;
+        }
+
+        match self.base.to_lowercase().as_str() {
+            "ca" => "can",
+            "wo" => "will",
+            "sha" => "shall",
+            _ => &self.base,
+        }
+    }
+
+    /// The word the clitic stands for, like `not` for `n't` and `will` for `'ll`.
+    ///
+    /// Returns `None` for `'s` and `'d`, which are ambiguous (`is`/`has`/possessive, `had`/`would`).
+    pub fn expanded_clitic(&self) -> Option<&'static str> {
+        match normalize_apostrophe(&self.clitic).as_str() {
+            "n't" => Some("not"),
+            "'ll" => Some("will"),
+            "'ve" => Some("have"),
+            "'re" => Some("are"),
+            "'m" => Some("am"),
+            _ => None,
+        }
+    }
+
+    /// Whether the clitic is `n't`
+    pub fn is_negation(&self) -> bool {
+        normalize_apostrophe(&self.clitic) == "n't"
+    }
+}
+
+fn normalize_apostrophe(text: &str) -> String {
+    text.to_lowercase().replace('’', "'")
+}
+
+/// Opt-in resolver assigning a [Contraction] to [TextTag::WORD] tokens like `don't`, `should've` or `I’m`.
+///
+/// The token itself is left untouched, so resolvers matching on whole words keep working.
+pub struct ContractionResolver;
+
+impl Resolver for ContractionResolver {
+    type Attr = Contraction;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .filter_map(|(selection, (_, text))| {
+                split_contraction(text).map(|(base, clitic)| {
+                    selection.finish_with_attr(Contraction {
+                        base: base.to_string(),
+                        clitic: clitic.to_string(),
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+/// Splits `text` into its base and clitic
+fn split_contraction(text: &str) -> Option<(&str, &str)> {
+    let (apostrophe_idx, apostrophe) =
+        text.char_indices().rev().find(|(_, c)| is_apostrophe(*c))?;
+    let suffix = &text[apostrophe_idx + apostrophe.len_utf8()..];
+
+    let clitic_idx = if suffix.eq_ignore_ascii_case("t") {
+        // "n't" starts before the apostrophe
+        let (n_idx, n) = text[..apostrophe_idx].char_indices().next_back()?;
+        if !n.eq_ignore_ascii_case(&'n') {
+            return None;
+        }
+        n_idx
+    } else if ["ll", "ve", "re", "m", "d", "s"]
+        .iter()
+        .any(|clitic| suffix.eq_ignore_ascii_case(clitic))
+    {
+        apostrophe_idx
+    } else {
+        return None;
+    };
+
+    let base = &text[..clitic_idx];
+    if !base.chars().any(char::is_alphabetic) {
+        return None;
+    }
+
+    Some((base, &text[clitic_idx..]))
+}
+
+fn is_apostrophe(c: char) -> bool {
+    c == '\'' || c == '’'
+}
+
+#[test]
+fn test_split_contraction() {
+    assert_eq!(split_contraction("don't"), Some(("do", "n't")));
+    assert_eq!(split_contraction("Can’t"), Some(("Ca", "n’t")));
+    assert_eq!(split_contraction("should've"), Some(("should", "'ve")));
+    assert_eq!(split_contraction("I'm"), Some(("I", "'m")));
+    assert_eq!(split_contraction("Bob's"), Some(("Bob", "'s")));
+    // not
+    assert_eq!(split_contraction("o'clock"), None);
+    assert_eq!(split_contraction("cameras'"), None);
+    assert_eq!(split_contraction("n't"), None);
+    assert_eq!(split_contraction("rock-n-roll"), None);
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line =
+        create_line_from_string("I can't say we’ll go, they don't").run(&ContractionResolver);
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Contraction>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    I     can't     say     we’ll     go  ,     they     don't
+          ╰───╯Contraction { base: "ca", clitic: "n't" }
+                            ╰───╯Contraction { base: "we", clitic: "’ll" }
+                                                         ╰───╯Contraction { base: "do", clitic: "n't" }
+    "###);
+
+    let expansions: Vec<_> = ll_line
+        .query::<Contraction>()
+        .into_iter()
+        .flat_map(|(_, _, contractions)| contractions)
+        .map(|contraction| (contraction.expanded_base(), contraction.expanded_clitic()))
+        .collect();
+
+    assert_eq!(
+        expansions,
+        vec![
+            ("can", Some("not")),
+            ("we", Some("will")),
+            ("do", Some("not")),
+        ]
+    );
+}
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::x::FindTexts;
+use crate::{LLCursorAssignment, LLSelection, Resolver};
+
+/// Attribute assigned by [FuzzyTextMatchAssignResolver]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch<T> {
+    pub value: T,
+    /// Edits (insertions, deletions, substitutions, transpositions) between the text and the key
+    pub distance: usize,
+}
+
+/// Damerau-Levenshtein distance between `a` and `b`, by `char`.
+///
+/// Unlike the optimal string alignment variant, this is a metric, which the [BKTree] relies on.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let max = a.len() + b.len();
+    let width = b.len() + 2;
+    // (a.len() + 2) x (b.len() + 2), the first row and column hold `max`
+    let mut d = vec![0; (a.len() + 2) * width];
+    let mut last_row: HashMap<char, usize> = HashMap::new();
+
+    d[0] = max;
+    for i in 0..=a.len() {
+        d[(i + 1) * width] = max;
+        d[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=b.len() {
+        d[j + 1] = max;
+        d[width + j + 1] = j;
+    }
+
+    for i in 1..=a.len() {
+        let mut last_col = 0;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_col;
+            let cost = if a[i - 1] == b[j - 1] {
+                last_col = j;
+                0
+            } else {
+                1
+            };
+            d[(i + 1) * width + j + 1] = (d[i * width + j] + cost)
+                .min(d[(i + 1) * width + j] + 1)
+                .min(d[i * width + j + 1] + 1)
+                .min(d[k * width + l] + (i - k - 1) + 1 + (j - l - 1));
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    d[(a.len() + 1) * width + b.len() + 1]
+}
+
+struct BKNode<T> {
+    key: Vec<char>,
+    values: Vec<T>,
+    /// (distance to `key`, node index)
+    children: Vec<(usize, usize)>,
+}
+
+/// Index of the lookup keys by edit distance, so a query only compares against a few keys
+struct BKTree<T> {
+    nodes: Vec<BKNode<T>>,
+}
+
+impl<T> BKTree<T> {
+    fn new() -> Self {
+        BKTree { nodes: Vec::new() }
+    }
+
+    fn insert(&mut self, key: Vec<char>, values: Vec<T>) {
+        let new_idx = self.nodes.len();
+        let mut idx = 0;
+        while idx < new_idx {
+            let distance = edit_distance(&self.nodes[idx].key, &key);
+            if distance == 0 {
+                self.nodes[idx].values.extend(values);
+                return;
+            }
+            match self.nodes[idx]
+                .children
+                .iter()
+                .find(|(child_distance, _)| *child_distance == distance)
+            {
+                Some((_, child_idx)) => idx = *child_idx,
+                None => {
+                    self.nodes[idx].children.push((distance, new_idx));
+                    break;
+                }
+            }
+        }
+        self.nodes.push(BKNode {
+            key,
+            values,
+            children: Vec::new(),
+        });
+    }
+
+    /// Nodes within `max_distance` of `query`, with their distance
+    fn find(&self, query: &[char], max_distance: usize) -> Vec<(&BKNode<T>, usize)> {
+        let mut found = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            Vec::new()
+        } else {
+            vec![0]
+        };
+
+        while let Some(idx) = stack.pop() {
+            let node = &self.nodes[idx];
+            let distance = edit_distance(&node.key, query);
+            if distance <= max_distance {
+                found.push((node, distance));
+            }
+            // triangle inequality: only children at distance ± max_distance can be close enough
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(child_distance, _)| child_distance.abs_diff(distance) <= max_distance)
+                    .map(|(_, child_idx)| *child_idx),
+            );
+        }
+
+        found
+    }
+}
+
+/// Default for [FuzzyTextMatchAssignResolver::with_max_distance]: no typos under 4 characters,
+/// 1 up to 7 characters, then 2.
+fn default_max_distance(len: usize) -> usize {
+    match len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    }
+}
+
+/// Like [TextMatchAssignResolver](super::TextMatchAssignResolver), but tolerant of typos, see
+/// [TextMatchAssignResolver::fuzzy](super::TextMatchAssignResolver::fuzzy).
+///
+/// Only the closest keys to a text are assigned, as [FuzzyMatch] with their edit distance.
+pub struct FuzzyTextMatchAssignResolver<T> {
+    case_sensitive: bool,
+    find_texts: FindTexts,
+    max_distance: fn(usize) -> usize,
+    tree: BKTree<T>,
+}
+
+impl<T> FuzzyTextMatchAssignResolver<T> {
+    pub(crate) fn new(
+        case_sensitive: bool,
+        find_texts: FindTexts,
+        lookup: HashMap<String, Vec<T>>,
+    ) -> Self {
+        let mut tree = BKTree::new();
+        for (key, values) in lookup {
+            tree.insert(key.chars().collect(), values);
+        }
+        FuzzyTextMatchAssignResolver {
+            case_sensitive,
+            find_texts,
+            max_distance: default_max_distance,
+            tree,
+        }
+    }
+
+    /// Edits allowed for a text of `len` characters
+    pub fn with_max_distance(mut self, max_distance: fn(usize) -> usize) -> Self {
+        self.max_distance = max_distance;
+        self
+    }
+}
+
+impl<T: Debug + Clone + 'static + Send + Sync> Resolver for FuzzyTextMatchAssignResolver<T> {
+    type Attr = FuzzyMatch<T>;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        (self.find_texts)(&selection)
+            .into_iter()
+            .flat_map(|(selection, text)| {
+                let query: Vec<char> = if self.case_sensitive {
+                    text.chars().collect()
+                } else {
+                    text.to_lowercase().chars().collect()
+                };
+                let found = self.tree.find(&query, (self.max_distance)(query.len()));
+                let closest = found.iter().map(|(_, distance)| *distance).min();
+
+                found
+                    .into_iter()
+                    .filter(move |(_, distance)| Some(*distance) == closest)
+                    .flat_map(|(node, distance)| {
+                        node.values
+                            .iter()
+                            .cloned()
+                            .map(move |value| FuzzyMatch { value, distance })
+                    })
+                    .map(move |attr| selection.finish_with_attr(attr))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_edit_distance() {
+    let distance = |a: &str, b: &str| {
+        edit_distance(
+            &a.chars().collect::<Vec<_>>(),
+            &b.chars().collect::<Vec<_>>(),
+        )
+    };
+
+    assert_eq!(distance("slack", "slack"), 0);
+    assert_eq!(distance("slakc", "slack"), 1);
+    assert_eq!(distance("algoila", "algolia"), 1);
+    assert_eq!(distance("wolfrm", "wolfram"), 1);
+    assert_eq!(distance("ca", "abc"), 2);
+    assert_eq!(distance("", "magic"), 5);
+    assert_eq!(distance("señor", "senor"), 1);
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay, TextMatchAssignResolver};
+
+    #[derive(Debug, Clone)]
+    enum Service {
+        Slack,
+        Algolia,
+        Magic,
+        Wolfram,
+    }
+
+    let ll_line = create_line_from_string("when Slakc hears a message, query Algoila or mgic").run(
+        &TextMatchAssignResolver::new_case_insensitive_str_arr([
+            ("Slack", Service::Slack),
+            ("Algolia", Service::Algolia),
+            ("Magic", Service::Magic),
+            ("Wolfram", Service::Wolfram),
+        ])
+        .fuzzy(),
+    );
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<FuzzyMatch<Service>>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    when     Slakc     hears     a     message  ,     query     Algoila     or     mgic
+             ╰───╯FuzzyMatch { value: Slack, distance: 1 }
+                                                                ╰─────╯FuzzyMatch { value: Algolia, distance: 1 }
+                                                                                   ╰──╯FuzzyMatch { value: Magic, distance: 1 }
+    "###);
+}
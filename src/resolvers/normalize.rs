@@ -0,0 +1,117 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+/// Normalized text of a [TextTag::WORD] token, match it with [x::normalized_text()](crate::x::normalized_text).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Normalized(pub String);
+
+impl Normalized {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Normalized {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Assigns [Normalized] to [TextTag::WORD] tokens, so resolvers can match words without
+/// each handling case and Unicode variants themselves.
+///
+/// Text is normalized by mapping curly quotes to straight ones, applying Unicode NFKC and
+/// lowercasing. Stripping diacritics (`café` to `cafe`) is opt-in.
+#[derive(Debug, Clone, Default)]
+pub struct NormalizeResolver {
+    strip_diacritics: bool,
+}
+
+impl NormalizeResolver {
+    pub fn new() -> Self {
+        NormalizeResolver::default()
+    }
+
+    /// Also remove diacritics, so `Café` is normalized to `cafe`
+    pub fn with_diacritics_stripped(mut self) -> Self {
+        self.strip_diacritics = true;
+        self
+    }
+
+    /// Normalize `text` like the resolver does, useful to normalize the words to look up.
+    pub fn normalize(&self, text: &str) -> String {
+        let text: String = text
+            .chars()
+            .map(|c| match c {
+                '‘' | '’' | '‚' | '‛' | '′' => '\'',
+                '“' | '”' | '„' | '‟' | '″' => '"',
+                _ => c,
+            })
+            .nfkc()
+            .collect::<String>()
+            .to_lowercase();
+
+        if self.strip_diacritics {
+            text.nfd()
+                .filter(|c| !is_combining_mark(*c))
+                .nfc()
+                .collect()
+        } else {
+            text
+        }
+    }
+}
+
+impl Resolver for NormalizeResolver {
+    type Attr = Normalized;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .map(|(selection, (_, text))| {
+                selection.finish_with_attr(Normalized(self.normalize(text)))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_normalize() {
+    let resolver = NormalizeResolver::new();
+    assert_eq!(resolver.normalize("Don’t"), "don't");
+    assert_eq!(resolver.normalize("ＡＢＣ"), "abc");
+    assert_eq!(resolver.normalize("ﬁne"), "fine");
+    assert_eq!(resolver.normalize("Café"), "café");
+
+    let resolver = NormalizeResolver::new().with_diacritics_stripped();
+    assert_eq!(resolver.normalize("Café"), "cafe");
+    assert_eq!(resolver.normalize("Ångström"), "angstrom");
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string("The CAFÉ’s ﬁne, 42")
+        .run(&NormalizeResolver::new().with_diacritics_stripped());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Normalized>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    The     CAFÉ’s     ﬁne  ,     42
+    ╰─╯Normalized("the")
+            ╰────╯Normalized("cafe's")
+                       ╰─╯Normalized("fine")
+    "###);
+
+    let words: Vec<&str> = ll_line
+        .find(&x::normalized_text())
+        .into_iter()
+        .map(|find| *find.attr())
+        .collect();
+    assert_eq!(words, vec!["the", "cafe's", "fine"]);
+}
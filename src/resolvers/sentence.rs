@@ -0,0 +1,80 @@
+use crate::{LLCursorAssignment, LLSelection, LToken, PositionUnit, Resolver, SentenceSegmenter};
+
+/// Assigned to the tokens of each sentence, without the whitespace between sentences.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sentence;
+
+/// Marks [Sentence]s found by a [SentenceSegmenter] on a line containing several sentences.
+///
+/// Use [SentenceSegmenter::create_lines] instead to create a line for each sentence.
+#[derive(Debug, Clone, Default)]
+pub struct SentenceResolver {
+    segmenter: SentenceSegmenter,
+}
+
+impl SentenceResolver {
+    pub fn new(segmenter: SentenceSegmenter) -> Self {
+        SentenceResolver { segmenter }
+    }
+}
+
+impl Resolver for SentenceResolver {
+    type Attr = Sentence;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        let ll_tokens = selection.ll_tokens();
+        let text_start = ll_tokens[0].starts_at(PositionUnit::Byte);
+
+        let mut text = String::new();
+        for ll_token in ll_tokens {
+            if let LToken::Text(token_text, _) = ll_token.get_token() {
+                text.push_str(token_text);
+            }
+        }
+
+        self.segmenter
+            .sentence_ranges(&text)
+            .into_iter()
+            .filter_map(|(start, end)| {
+                // tokens starting within the sentence
+                let (start, end) = (text_start + start, text_start + end);
+                let first = ll_tokens
+                    .iter()
+                    .find(|ll_token| ll_token.starts_at(PositionUnit::Byte) >= start)?;
+                let last = ll_tokens
+                    .iter()
+                    .rev()
+                    .find(|ll_token| ll_token.starts_at(PositionUnit::Byte) < end)?;
+
+                selection
+                    .selection_from(first.token_idx, last.token_idx)
+                    .map(|sentence| sentence.finish_with_attr(Sentence))
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_input_tokens, InputToken, LLLineDisplay};
+
+    let ll_line = create_line_from_input_tokens(
+        vec![
+            InputToken::text("Dr. Who left. ".to_string(), Vec::new()),
+            InputToken::custom(1, Vec::new()),
+            InputToken::text(" Why?  Ok".to_string(), Vec::new()),
+        ],
+        |text| text.len(),
+    )
+    .run(&SentenceResolver::default());
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Sentence>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    Dr  .     Who     left  .     <>     Why  ?      Ok
+    ╰───────────────────────╯Sentence
+                                         ╰────╯Sentence
+                                                     ╰╯Sentence
+    "###);
+}
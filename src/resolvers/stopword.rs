@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+
+use crate::{x, LLCursorAssignment, LLSelection, Resolver, TextTag};
+
+const ENGLISH: &[&str] = &[
+    "a",
+    "about",
+    "above",
+    "after",
+    "again",
+    "against",
+    "all",
+    "am",
+    "an",
+    "and",
+    "any",
+    "are",
+    "as",
+    "at",
+    "be",
+    "because",
+    "been",
+    "before",
+    "being",
+    "below",
+    "between",
+    "both",
+    "but",
+    "by",
+    "can",
+    "could",
+    "did",
+    "do",
+    "does",
+    "doing",
+    "down",
+    "during",
+    "each",
+    "few",
+    "for",
+    "from",
+    "further",
+    "had",
+    "has",
+    "have",
+    "having",
+    "he",
+    "her",
+    "here",
+    "hers",
+    "herself",
+    "him",
+    "himself",
+    "his",
+    "how",
+    "i",
+    "if",
+    "in",
+    "into",
+    "is",
+    "it",
+    "its",
+    "itself",
+    "just",
+    "me",
+    "more",
+    "most",
+    "my",
+    "myself",
+    "no",
+    "nor",
+    "not",
+    "now",
+    "of",
+    "off",
+    "on",
+    "once",
+    "only",
+    "or",
+    "other",
+    "our",
+    "ours",
+    "ourselves",
+    "out",
+    "over",
+    "own",
+    "same",
+    "she",
+    "should",
+    "so",
+    "some",
+    "such",
+    "than",
+    "that",
+    "the",
+    "their",
+    "theirs",
+    "them",
+    "themselves",
+    "then",
+    "there",
+    "these",
+    "they",
+    "this",
+    "those",
+    "through",
+    "to",
+    "too",
+    "under",
+    "until",
+    "up",
+    "very",
+    "was",
+    "we",
+    "were",
+    "what",
+    "when",
+    "where",
+    "which",
+    "while",
+    "who",
+    "whom",
+    "why",
+    "will",
+    "with",
+    "would",
+    "you",
+    "your",
+    "yours",
+    "yourself",
+    "yourselves",
+];
+
+const FRENCH: &[&str] = &[
+    "à", "au", "aux", "avec", "ce", "ces", "cette", "dans", "de", "des", "du", "elle", "elles",
+    "en", "est", "et", "eux", "il", "ils", "je", "la", "le", "les", "leur", "leurs", "lui", "ma",
+    "mais", "me", "mes", "moi", "mon", "ne", "nos", "notre", "nous", "on", "ou", "où", "par",
+    "pas", "pour", "qu", "que", "qui", "sa", "se", "ses", "son", "sur", "ta", "te", "tes", "toi",
+    "ton", "tu", "un", "une", "vos", "votre", "vous", "y",
+];
+
+const GERMAN: &[&str] = &[
+    "aber", "als", "am", "an", "auch", "auf", "aus", "bei", "bin", "bis", "da", "das", "dass",
+    "dem", "den", "der", "des", "die", "dies", "diese", "du", "durch", "ein", "eine", "einem",
+    "einen", "einer", "eines", "er", "es", "für", "hat", "ich", "ihr", "im", "in", "ist", "ja",
+    "mit", "nach", "nicht", "noch", "nur", "oder", "sich", "sie", "sind", "so", "über", "um",
+    "und", "uns", "von", "vor", "war", "was", "wie", "wir", "zu", "zum", "zur",
+];
+
+const SPANISH: &[&str] = &[
+    "a", "al", "algo", "como", "con", "de", "del", "el", "ella", "ellas", "ellos", "en", "entre",
+    "era", "es", "esta", "este", "esto", "fue", "ha", "la", "las", "le", "les", "lo", "los", "mas",
+    "me", "mi", "muy", "no", "nos", "o", "para", "pero", "por", "que", "se", "si", "sin", "sobre",
+    "su", "sus", "también", "te", "tu", "un", "una", "uno", "y", "ya", "yo",
+];
+
+/// Languages with a built-in list of stopwords
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopwordLanguage {
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+impl StopwordLanguage {
+    fn words(self) -> &'static [&'static str] {
+        match self {
+            StopwordLanguage::English => ENGLISH,
+            StopwordLanguage::French => FRENCH,
+            StopwordLanguage::German => GERMAN,
+            StopwordLanguage::Spanish => SPANISH,
+        }
+    }
+}
+
+/// Assigned to function words like "the", "a" or "of", see [x::seq_ignoring_stopwords](crate::x::seq_ignoring_stopwords).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stopword;
+
+/// Assigns [Stopword] to [TextTag::WORD] tokens in its list, compared case insensitively.
+#[derive(Debug, Clone)]
+pub struct StopwordResolver {
+    /// lowercase
+    words: HashSet<String>,
+}
+
+impl Default for StopwordResolver {
+    /// English stopwords
+    fn default() -> Self {
+        StopwordResolver::new(StopwordLanguage::English)
+    }
+}
+
+impl StopwordResolver {
+    /// Resolver with the built-in list of `language`
+    pub fn new(language: StopwordLanguage) -> Self {
+        StopwordResolver::custom(language.words().iter().copied())
+    }
+
+    /// Resolver with only `words` as stopwords
+    pub fn custom<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        StopwordResolver {
+            words: HashSet::new(),
+        }
+        .with_words(words)
+    }
+
+    /// Add `words` to the stopwords
+    pub fn with_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.words
+            .extend(words.into_iter().map(|word| word.as_ref().to_lowercase()));
+        self
+    }
+
+    /// Don't treat `words` as stopwords
+    pub fn without_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in words {
+            self.words.remove(&word.as_ref().to_lowercase());
+        }
+        self
+    }
+
+    pub fn is_stopword(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+}
+
+impl Resolver for StopwordResolver {
+    type Attr = Stopword;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::all((x::attr_eq(&TextTag::WORD), x::token_text())))
+            .into_iter()
+            .filter(|(_, (_, word))| self.is_stopword(word))
+            .map(|(selection, _)| selection.finish_with_attr(Stopword))
+            .collect()
+    }
+}
+
+#[test]
+fn test() {
+    use crate::{create_line_from_string, LLLineDisplay};
+
+    let ll_line = create_line_from_string("The Bank of  America, and the bank")
+        .run(&StopwordResolver::default().without_words(["and"]));
+
+    let mut ll_display = LLLineDisplay::new(&ll_line);
+    ll_display.include::<Stopword>();
+
+    insta::assert_snapshot!(ll_display, @r###"
+    The     Bank     of      America  ,     and     the     bank
+    ╰─╯Stopword
+                     ╰╯Stopword
+                                                    ╰─╯Stopword
+    "###);
+
+    let selection = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+    let word = || x::all((x::attr_eq(&TextTag::WORD), x::token_text()));
+    let phrases: Vec<_> = selection
+        .find_by(&x::seq_ignoring_stopwords((word(), word())))
+        .into_iter()
+        .map(|(_, ((_, a), (_, b)))| (a, b))
+        .collect();
+    assert_eq!(phrases, vec![("Bank", "America"), ("and", "bank")]);
+
+    let resolver = StopwordResolver::new(StopwordLanguage::German).with_words(["Bitte"]);
+    assert!(resolver.is_stopword("Über"));
+    assert!(resolver.is_stopword("bitte"));
+    assert!(!resolver.is_stopword("the"));
+}
@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 
-use crate::{x, Resolver};
+use super::FuzzyTextMatchAssignResolver;
+use crate::{x, LLSelection, Resolver};
 
 /// Useful for name matching.
 ///
 /// Simple resolver for mapping a string matching a single token to an attribute.
 pub struct TextMatchAssignResolver<T> {
     case_sensitive: bool,
+    find_texts: x::FindTexts,
     // $ tokens $$ text with spaces $$$ regex?
     lookup: HashMap<String, Vec<T>>,
 }
@@ -16,12 +18,14 @@ impl<T: Debug + Clone + 'static> TextMatchAssignResolver<T> {
     pub fn new(lookup: HashMap<String, Vec<T>>) -> Self {
         TextMatchAssignResolver {
             case_sensitive: true,
+            find_texts: x::token_texts,
             lookup,
         }
     }
     pub fn new_case_insensitive(lookup: HashMap<String, Vec<T>>) -> Self {
         TextMatchAssignResolver {
             case_sensitive: false,
+            find_texts: x::token_texts,
             lookup: lookup
                 .into_iter()
                 .map(|(key, val)| (key.to_lowercase(), val))
@@ -31,20 +35,37 @@ impl<T: Debug + Clone + 'static> TextMatchAssignResolver<T> {
     pub fn new_case_insensitive_str_arr<const N: usize>(lookup: [(&'static str, T); N]) -> Self {
         TextMatchAssignResolver {
             case_sensitive: false,
+            find_texts: x::token_texts,
             lookup: lookup
                 .iter()
                 .map(|(key, val)| (key.to_lowercase(), vec![val.clone()]))
                 .collect(),
         }
     }
+
+    /// Look up the text of `A` attributes instead of the token text, like
+    /// [Normalized](crate::Normalized) or a lemma assigned by another resolver.
+    ///
+    /// The keys of the lookup need to be in the same form as that text.
+    pub fn match_on<A: AsRef<str> + 'static>(mut self) -> Self {
+        self.find_texts = x::attr_texts::<A>;
+        self
+    }
+
+    /// Also match texts a few typos away from the keys, like `Slakc` for `Slack`.
+    ///
+    /// The edits allowed grow with the length of the text, see
+    /// [FuzzyTextMatchAssignResolver::with_max_distance].
+    pub fn fuzzy(self) -> FuzzyTextMatchAssignResolver<T> {
+        FuzzyTextMatchAssignResolver::new(self.case_sensitive, self.find_texts, self.lookup)
+    }
 }
 
 impl<T: Debug + Clone + 'static + Send + Sync> Resolver for TextMatchAssignResolver<T> {
     type Attr = T;
 
-    fn go(&self, selection: crate::LLSelection) -> Vec<crate::LLCursorAssignment<Self::Attr>> {
-        selection
-            .find_by(&x::token_text())
+    fn go(&self, selection: LLSelection) -> Vec<crate::LLCursorAssignment<Self::Attr>> {
+        (self.find_texts)(&selection)
             .into_iter()
             .flat_map(|(selection, text)| {
                 if self.case_sensitive {
@@ -99,7 +120,7 @@ fn test() {
     let mut ll_display = LLLineDisplay::new(&ll_line);
     ll_display.include::<Service>();
 
-    insta::assert_display_snapshot!(ll_display, @r###"
+    insta::assert_snapshot!(ll_display, @r###"
     when     Slack     hears     a     message     in     #  general  Algolia     search     query  :     message  ,     table
              ╰───╯Slack
                                                                       ╰─────╯Algolia
@@ -0,0 +1,143 @@
+//! Adapters turning rich text into [InputToken]s, with its formatting as attributes.
+
+mod html;
+#[cfg(feature = "markdown")]
+mod markdown;
+
+pub use html::{html_to_input_tokens, HtmlInput, HtmlSourceMap};
+#[cfg(feature = "markdown")]
+pub use markdown::markdown_to_input_tokens;
+
+use crate::create_tokens::InputToken;
+use crate::type_bucket::AnyAttribute;
+
+/// Inline formatting of rich text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkKind {
+    /// `*italic*`, `<em>` or `<i>`
+    Italic,
+    /// `**bold**`, `<strong>` or `<b>`
+    Bold,
+    /// `~~strikethrough~~`, `<s>` or `<del>`
+    Strikethrough,
+    /// `` `code` `` spans, code blocks, `<code>` or `<pre>`
+    Code,
+}
+
+/// Target of a link, assigned to the link text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub href: String,
+    pub title: String,
+}
+
+/// Assigned to the text of a heading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heading {
+    /// From `1` to `6`
+    pub level: u8,
+}
+
+/// Assigned to the text of a list item, nested list items only get the innermost one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListItem {
+    pub ordered: bool,
+}
+
+/// Payload of the custom token created for an inline image.
+///
+/// Match it with [x::value::<Image>()](crate::x::value), its alt text is the token's label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    pub src: String,
+    pub title: String,
+    pub alt: String,
+}
+
+/// Attributes of a piece of text
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Formatting {
+    marks: Vec<MarkKind>,
+    link: Option<Link>,
+    heading: Option<Heading>,
+    list_item: Option<ListItem>,
+}
+
+impl Formatting {
+    fn attrs(&self) -> Vec<AnyAttribute> {
+        let mut attrs: Vec<AnyAttribute> =
+            self.marks.iter().copied().map(AnyAttribute::new).collect();
+        attrs.extend(self.link.clone().map(AnyAttribute::new));
+        attrs.extend(self.heading.map(AnyAttribute::new));
+        attrs.extend(self.list_item.map(AnyAttribute::new));
+        attrs
+    }
+}
+
+/// Collects [InputToken]s, keeping text with the same formatting together
+/// so words split up by escapes or entities aren't split up into several tokens.
+#[derive(Default)]
+struct InputBuilder {
+    input: Vec<InputToken>,
+    pending: Option<(String, Formatting)>,
+    /// bytes of text pushed so far
+    text_len: usize,
+    last_char: Option<char>,
+}
+
+impl InputBuilder {
+    fn push_text(&mut self, text: &str, formatting: &Formatting) {
+        if text.is_empty() {
+            return;
+        }
+
+        self.text_len += text.len();
+        self.last_char = text.chars().next_back();
+
+        if let Some((pending_text, pending_formatting)) = &mut self.pending {
+            if pending_formatting == formatting {
+                pending_text.push_str(text);
+                return;
+            }
+        }
+
+        self.flush();
+        self.pending = Some((text.to_string(), formatting.clone()));
+    }
+
+    /// Separate blocks with a new line, unless there is nothing to separate.
+    ///
+    /// Returns whether the line break was added.
+    fn push_line_break(&mut self) -> bool {
+        match self.last_char {
+            Some(last_char) if last_char != '\n' => {
+                self.push_text("\n", &Formatting::default());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Add a custom token of size `1` for the image
+    fn push_image(&mut self, image: Image, formatting: &Formatting) {
+        self.flush();
+        self.last_char = Some('\u{FFFC}');
+        self.input.push(InputToken::value(
+            1,
+            image.alt.clone(),
+            image,
+            formatting.attrs(),
+        ));
+    }
+
+    fn flush(&mut self) {
+        if let Some((text, formatting)) = self.pending.take() {
+            self.input.push(InputToken::text(text, formatting.attrs()));
+        }
+    }
+
+    fn finish(mut self) -> Vec<InputToken> {
+        self.flush();
+        self.input
+    }
+}
@@ -0,0 +1,728 @@
+use super::{Formatting, Heading, Image, InputBuilder, Link, ListItem, MarkKind};
+use crate::create_tokens::InputToken;
+
+/// Result of [html_to_input_tokens]
+pub struct HtmlInput {
+    pub input_tokens: Vec<InputToken>,
+    /// Maps positions in the text of the input tokens back to the HTML source
+    pub source_map: HtmlSourceMap,
+}
+
+/// Maps byte positions in the text extracted from HTML back to byte positions in the HTML source.
+///
+/// Text positions can be taken from [LLLineFind::range_in](crate::ll_line::LLLineFind::range_in)
+/// with [PositionUnit::Byte](crate::PositionUnit::Byte). Custom tokens created for images don't take up
+/// any text, so they can't be mapped back.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlSourceMap {
+    segments: Vec<SourceSegment>,
+}
+
+/// Text which comes from a piece of HTML, the text and the HTML are the same if they have the same length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SourceSegment {
+    text_start: usize,
+    text_end: usize,
+    html_start: usize,
+    html_end: usize,
+}
+
+impl SourceSegment {
+    fn is_verbatim(&self) -> bool {
+        self.text_end - self.text_start == self.html_end - self.html_start
+    }
+}
+
+impl HtmlSourceMap {
+    /// Map the text from byte `start` up to byte `end` to the range of HTML it was extracted from.
+    ///
+    /// Positions inside decoded entities or collapsed whitespace are widened to the whole entity or whitespace.
+    /// Returns `None` for empty or out of bounds ranges.
+    pub fn html_range(&self, (start, end): (usize, usize)) -> Option<(usize, usize)> {
+        if start >= end {
+            return None;
+        }
+
+        let first = self
+            .segments
+            .get(self.segments.partition_point(|seg| seg.text_end <= start))?;
+        let last = self
+            .segments
+            .get(self.segments.partition_point(|seg| seg.text_end < end))?;
+
+        let html_start = if first.is_verbatim() {
+            first.html_start + start - first.text_start
+        } else {
+            first.html_start
+        };
+        let html_end = if last.is_verbatim() {
+            last.html_start + end - last.text_start
+        } else {
+            last.html_end
+        };
+
+        Some((html_start, html_end))
+    }
+
+    fn record(&mut self, text_start: usize, text_len: usize, html_start: usize, html_end: usize) {
+        let segment = SourceSegment {
+            text_start,
+            text_end: text_start + text_len,
+            html_start,
+            html_end,
+        };
+
+        if let Some(last) = self.segments.last_mut() {
+            if last.is_verbatim()
+                && segment.is_verbatim()
+                && last.text_end == segment.text_start
+                && last.html_end == segment.html_start
+            {
+                last.text_end = segment.text_end;
+                last.html_end = segment.html_end;
+                return;
+            }
+        }
+
+        self.segments.push(segment);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ElementKind {
+    Mark(MarkKind),
+    /// `<pre>`, keeps whitespace
+    Preformatted,
+    Link(Link),
+    Heading(Heading),
+    List {
+        ordered: bool,
+    },
+    ListItem(ListItem),
+    Other,
+}
+
+struct OpenElement {
+    name: String,
+    kind: ElementKind,
+}
+
+struct Tag {
+    /// lowercase
+    name: String,
+    closing: bool,
+    self_closing: bool,
+    attrs: Vec<(String, String)>,
+}
+
+impl Tag {
+    fn attr(&self, name: &str) -> String {
+        self.attrs
+            .iter()
+            .find(|(attr_name, _)| attr_name == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Elements separated from the surrounding text by a line break
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "address"
+            | "article"
+            | "aside"
+            | "blockquote"
+            | "body"
+            | "dd"
+            | "div"
+            | "dl"
+            | "dt"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "form"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "header"
+            | "html"
+            | "li"
+            | "main"
+            | "nav"
+            | "ol"
+            | "p"
+            | "pre"
+            | "section"
+            | "table"
+            | "td"
+            | "th"
+            | "tr"
+            | "ul"
+    )
+}
+
+/// Elements without content or closing tag
+fn is_void(name: &str) -> bool {
+    matches!(
+        name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "param"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Elements whose content isn't text to analyze
+fn is_skipped(name: &str) -> bool {
+    matches!(name, "script" | "style" | "template" | "title")
+}
+
+struct HtmlConverter {
+    input: InputBuilder,
+    source_map: HtmlSourceMap,
+    open_elements: Vec<OpenElement>,
+}
+
+impl HtmlConverter {
+    fn formatting(&self) -> Formatting {
+        let mut formatting = Formatting::default();
+        for element in &self.open_elements {
+            let mark = match &element.kind {
+                ElementKind::Mark(mark) => Some(*mark),
+                ElementKind::Preformatted => Some(MarkKind::Code),
+                ElementKind::Link(link) => {
+                    formatting.link = Some(link.clone());
+                    None
+                }
+                ElementKind::Heading(heading) => {
+                    formatting.heading = Some(*heading);
+                    None
+                }
+                ElementKind::ListItem(list_item) => {
+                    formatting.list_item = Some(*list_item);
+                    None
+                }
+                ElementKind::List { .. } | ElementKind::Other => None,
+            };
+
+            if let Some(mark) = mark {
+                if !formatting.marks.contains(&mark) {
+                    formatting.marks.push(mark);
+                }
+            }
+        }
+        formatting
+    }
+
+    fn push(&mut self, text: &str, html_start: usize, html_end: usize, formatting: &Formatting) {
+        let text_start = self.input.text_len;
+        self.input.push_text(text, formatting);
+        self.source_map
+            .record(text_start, text.len(), html_start, html_end);
+    }
+
+    fn push_line_break(&mut self, html_start: usize, html_end: usize) {
+        let text_start = self.input.text_len;
+        if self.input.push_line_break() {
+            self.source_map.record(text_start, 1, html_start, html_end);
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        if let Some(idx) = self
+            .open_elements
+            .iter()
+            .rposition(|element| element.name == name)
+        {
+            self.open_elements.truncate(idx);
+        }
+    }
+
+    fn tag(&mut self, tag: Tag, html_start: usize, html_end: usize) {
+        if tag.closing {
+            if is_block(&tag.name) {
+                self.push_line_break(html_start, html_end);
+            }
+            self.close(&tag.name);
+            return;
+        }
+
+        match tag.name.as_str() {
+            "br" | "hr" => {
+                self.push_line_break(html_start, html_end);
+                return;
+            }
+            "img" => {
+                let image = Image {
+                    src: tag.attr("src"),
+                    title: tag.attr("title"),
+                    alt: tag.attr("alt"),
+                };
+                let formatting = self.formatting();
+                self.input.push_image(image, &formatting);
+                return;
+            }
+            name if is_void(name) => return,
+            _ => {}
+        }
+
+        // paragraphs and list items are often left open
+        if is_block(&tag.name)
+            && self
+                .open_elements
+                .last()
+                .is_some_and(|element| element.name == "p")
+        {
+            self.open_elements.pop();
+        }
+        if tag.name == "li" {
+            let list_idx = self
+                .open_elements
+                .iter()
+                .rposition(|element| matches!(element.kind, ElementKind::List { .. }));
+            if let Some(item_idx) = self
+                .open_elements
+                .iter()
+                .rposition(|element| element.name == "li")
+            {
+                if list_idx.is_none_or(|list_idx| item_idx > list_idx) {
+                    self.open_elements.truncate(item_idx);
+                }
+            }
+        }
+
+        if is_block(&tag.name) {
+            self.push_line_break(html_start, html_end);
+        }
+        if tag.self_closing {
+            return;
+        }
+
+        let kind = match tag.name.as_str() {
+            "em" | "i" => ElementKind::Mark(MarkKind::Italic),
+            "strong" | "b" => ElementKind::Mark(MarkKind::Bold),
+            "s" | "del" | "strike" => ElementKind::Mark(MarkKind::Strikethrough),
+            "code" | "kbd" | "samp" | "tt" => ElementKind::Mark(MarkKind::Code),
+            "pre" => ElementKind::Preformatted,
+            "a" => ElementKind::Link(Link {
+                href: tag.attr("href"),
+                title: tag.attr("title"),
+            }),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => ElementKind::Heading(Heading {
+                level: tag.name.as_bytes()[1] - b'0',
+            }),
+            "ul" => ElementKind::List { ordered: false },
+            "ol" => ElementKind::List { ordered: true },
+            "li" => ElementKind::ListItem(ListItem {
+                ordered: self
+                    .open_elements
+                    .iter()
+                    .rev()
+                    .find_map(|element| match element.kind {
+                        ElementKind::List { ordered } => Some(ordered),
+                        _ => None,
+                    })
+                    .unwrap_or_default(),
+            }),
+            _ => ElementKind::Other,
+        };
+
+        self.open_elements.push(OpenElement {
+            name: tag.name,
+            kind,
+        });
+    }
+
+    fn text(&mut self, text: &str, html_start: usize) {
+        let formatting = self.formatting();
+        let preformatted = self
+            .open_elements
+            .iter()
+            .any(|element| element.kind == ElementKind::Preformatted);
+
+        let mut idx = 0;
+        while idx < text.len() {
+            let rest = &text[idx..];
+            let html_idx = html_start + idx;
+
+            if let Some((decoded, len)) = decode_entity(rest) {
+                self.push(&decoded, html_idx, html_idx + len, &formatting);
+                idx += len;
+            } else if !preformatted && rest.starts_with(|c: char| c.is_ascii_whitespace()) {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_whitespace())
+                    .unwrap_or(rest.len());
+                // collapse whitespace, and leave it out at the start of blocks
+                if self
+                    .input
+                    .last_char
+                    .is_some_and(|last_char| !last_char.is_whitespace())
+                {
+                    self.push(" ", html_idx, html_idx + len, &formatting);
+                }
+                idx += len;
+            } else {
+                let len = rest
+                    .char_indices()
+                    .skip(1)
+                    .find(|(_, c)| *c == '&' || (!preformatted && c.is_ascii_whitespace()))
+                    .map_or(rest.len(), |(len, _)| len);
+                self.push(&rest[..len], html_idx, html_idx + len, &formatting);
+                idx += len;
+            }
+        }
+    }
+}
+
+/// Convert HTML into [InputToken]s, with formatting as [MarkKind], [Link], [Heading]
+/// and [ListItem] attributes, and a [HtmlSourceMap] to map text positions back to the HTML.
+///
+/// Markup is left out and whitespace is collapsed (except in `<pre>`). Block elements and
+/// `<br>` are separated by a line break. Images become custom tokens of size `1` with an [Image] payload.
+///
+/// ```
+/// use layered_nlp::{create_line_from_input_tokens, html_to_input_tokens, x, PositionUnit};
+///
+/// let html = "<p>Hello <b>World</b></p>";
+/// let html_input = html_to_input_tokens(html);
+/// let ll_line = create_line_from_input_tokens(html_input.input_tokens, |text| text.len());
+///
+/// let world = ll_line.find(&x::attr::<layered_nlp::MarkKind>());
+/// let html_range = html_input.source_map.html_range(world[0].range_in(PositionUnit::Byte));
+/// assert_eq!(html_range, Some((12, 17)));
+/// ```
+pub fn html_to_input_tokens(html: &str) -> HtmlInput {
+    let mut converter = HtmlConverter {
+        input: InputBuilder::default(),
+        source_map: HtmlSourceMap::default(),
+        open_elements: Vec::new(),
+    };
+    // same byte offsets as `html`
+    let lowercase_html = html.to_ascii_lowercase();
+
+    let mut idx = 0;
+    while idx < html.len() {
+        let rest = &html[idx..];
+
+        if rest.starts_with("<!--") {
+            idx = rest.find("-->").map_or(html.len(), |end| idx + end + 3);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            idx = rest.find('>').map_or(html.len(), |end| idx + end + 1);
+        } else if let Some((tag, len)) = parse_tag(rest) {
+            let tag_end = idx + len;
+            if !tag.closing && !tag.self_closing && is_skipped(&tag.name) {
+                // continue after the closing tag
+                let closing_tag = format!("</{}", tag.name);
+                idx = lowercase_html[tag_end..]
+                    .find(&closing_tag)
+                    .and_then(|closing_idx| {
+                        let closing_idx = tag_end + closing_idx;
+                        html[closing_idx..]
+                            .find('>')
+                            .map(|end| closing_idx + end + 1)
+                    })
+                    .unwrap_or(html.len());
+            } else {
+                converter.tag(tag, idx, tag_end);
+                idx = tag_end;
+            }
+        } else {
+            // text up to the next tag, or a `<` which doesn't start a tag
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let len = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |len| len + first);
+            converter.text(&rest[..len], idx);
+            idx += len;
+        }
+    }
+
+    HtmlInput {
+        input_tokens: converter.input.finish(),
+        source_map: converter.source_map,
+    }
+}
+
+/// Parse the tag at the start of `text`, returning it with its length
+fn parse_tag(text: &str) -> Option<(Tag, usize)> {
+    let bytes = text.as_bytes();
+    if bytes.first() != Some(&b'<') {
+        return None;
+    }
+    let mut idx = 1;
+
+    let closing = bytes.get(idx) == Some(&b'/');
+    if closing {
+        idx += 1;
+    }
+
+    if !bytes.get(idx)?.is_ascii_alphabetic() {
+        return None;
+    }
+    let name_start = idx;
+    while bytes
+        .get(idx)
+        .is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'-')
+    {
+        idx += 1;
+    }
+    let name = text[name_start..idx].to_ascii_lowercase();
+
+    let mut self_closing = false;
+    let mut attrs = Vec::new();
+    loop {
+        while bytes.get(idx)?.is_ascii_whitespace() {
+            idx += 1;
+        }
+
+        match bytes[idx] {
+            b'>' => break,
+            b'/' => {
+                self_closing = true;
+                idx += 1;
+                continue;
+            }
+            _ => {}
+        }
+
+        let attr_start = idx;
+        while !matches!(
+            bytes.get(idx)?,
+            b'=' | b'>' | b'/' | b' ' | b'\t' | b'\n' | b'\r'
+        ) {
+            idx += 1;
+        }
+        let attr_name = text[attr_start..idx].to_ascii_lowercase();
+        if attr_name.is_empty() {
+            // like a stray `=`
+            idx += 1;
+            continue;
+        }
+
+        while bytes.get(idx)?.is_ascii_whitespace() {
+            idx += 1;
+        }
+
+        let mut value = String::new();
+        if bytes[idx] == b'=' {
+            idx += 1;
+            while bytes.get(idx)?.is_ascii_whitespace() {
+                idx += 1;
+            }
+
+            let value_text = match bytes[idx] {
+                quote @ (b'"' | b'\'') => {
+                    let value_start = idx + 1;
+                    let value_len = text[value_start..].find(quote as char)?;
+                    idx = value_start + value_len + 1;
+                    &text[value_start..value_start + value_len]
+                }
+                _ => {
+                    let value_start = idx;
+                    while !bytes.get(idx)?.is_ascii_whitespace() && bytes[idx] != b'>' {
+                        idx += 1;
+                    }
+                    &text[value_start..idx]
+                }
+            };
+            value = decode_entities(value_text);
+        }
+
+        attrs.push((attr_name, value));
+    }
+
+    Some((
+        Tag {
+            name,
+            closing,
+            self_closing,
+            attrs,
+        },
+        idx + 1,
+    ))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::new();
+    let mut idx = 0;
+    while let Some(amp_idx) = text[idx..].find('&') {
+        let amp_idx = idx + amp_idx;
+        decoded.push_str(&text[idx..amp_idx]);
+        match decode_entity(&text[amp_idx..]) {
+            Some((entity, len)) => {
+                decoded.push_str(&entity);
+                idx = amp_idx + len;
+            }
+            None => {
+                decoded.push('&');
+                idx = amp_idx + 1;
+            }
+        }
+    }
+    decoded.push_str(&text[idx..]);
+    decoded
+}
+
+/// Decode the character reference at the start of `text`, returning it with its length
+fn decode_entity(text: &str) -> Option<(String, usize)> {
+    if !text.starts_with('&') {
+        return None;
+    }
+
+    let len = text.char_indices().take(32).find(|(_, c)| *c == ';')?.0 + 1;
+    let name = &text[1..len - 1];
+
+    let decoded = if let Some(number) = name.strip_prefix('#') {
+        let code = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+            None => number.parse().ok()?,
+        };
+        char::from_u32(code)?
+    } else {
+        match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{A0}',
+            "ndash" => '–',
+            "mdash" => '—',
+            "hellip" => '…',
+            "lsquo" => '‘',
+            "rsquo" => '’',
+            "ldquo" => '“',
+            "rdquo" => '”',
+            "copy" => '©',
+            "reg" => '®',
+            "trade" => '™',
+            "euro" => '€',
+            _ => return None,
+        }
+    };
+
+    Some((decoded.to_string(), len))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{decode_entities, html_to_input_tokens, HtmlInput};
+    use crate::create_tokens::create_line_from_input_tokens;
+    use crate::ll_line::{x, LLLineDisplay, PositionUnit};
+    use crate::rich_text::{Heading, Image, Link, ListItem, MarkKind};
+
+    #[test]
+    fn test_html_to_input_tokens() {
+        let html = r#"<html><head><title>Ignored</title><style>p { color: red }</style></head>
+<body>
+  <h2>Q&amp;A</h2>
+  <p>See <a href="https://example.com/?a=1&amp;b=2" title='Docs'><b>the   docs</b></a>,<br>or <em>ask</em> <img src="cat.png" alt="a cat"></p>
+  <!-- <p>comment</p> -->
+  <ol><li>one<li>two <code>x &lt; 1</code></ol>
+</body></html>"#;
+
+        let HtmlInput {
+            input_tokens,
+            source_map,
+        } = html_to_input_tokens(html);
+        let ll_line = create_line_from_input_tokens(input_tokens, |text| text.len());
+
+        let mut ll_line_display = LLLineDisplay::new(&ll_line);
+        ll_line_display.include::<MarkKind>();
+        ll_line_display.include::<Link>();
+        ll_line_display.include::<Heading>();
+        ll_line_display.include::<ListItem>();
+
+        insta::assert_snapshot!(ll_line_display, @r###"
+        Q  &  A  \n  See     the     docs  ,  \n  or     ask     <a cat>  \n  one  \n  two     x     <     1  \n
+                             ╰──────────╯Bold
+                                                         ╰─╯Italic
+                                                                                               ╰───────────╯Code
+                             ╰──────────╯Link { href: "https://example.com/?a=1&b=2", title: "Docs" }
+        ╰─────╯Heading { level: 2 }
+                                                                              ╰─╯ListItem { ordered: true }
+                                                                                       ╰────╯ListItem { ordered: true }
+                                                                                               ╰───────────╯ListItem { ordered: true }
+        "###);
+
+        let html_texts: Vec<&str> = ll_line
+            .find(&x::token_text())
+            .into_iter()
+            .filter(|find| ["Q", "&", "docs", "\n", "<"].contains(find.attr()))
+            .map(|find| {
+                let (start, end) = source_map
+                    .html_range(find.range_in(PositionUnit::Byte))
+                    .unwrap();
+                &html[start..end]
+            })
+            .collect();
+        insta::assert_debug_snapshot!(html_texts, @r###"
+        [
+            "Q",
+            "&amp;",
+            "</h2>",
+            "docs",
+            "<br>",
+            "</p>",
+            "<li>",
+            "&lt;",
+            "</ol>",
+        ]
+        "###);
+
+        let images: Vec<_> = ll_line
+            .find(&x::value::<Image>())
+            .into_iter()
+            .map(|find| *find.attr())
+            .collect();
+        assert_eq!(
+            images,
+            vec![&Image {
+                src: "cat.png".to_string(),
+                title: String::new(),
+                alt: "a cat".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_non_ascii_text() {
+        let HtmlInput {
+            input_tokens,
+            source_map,
+        } = html_to_input_tokens("<p>été</p>à < é");
+        let ll_line = create_line_from_input_tokens(input_tokens, |text| text.len());
+
+        insta::assert_snapshot!(LLLineDisplay::new(&ll_line), @r###"
+        été  \n  à     <     é
+        "###);
+
+        let (start, end) = source_map.html_range((0, "été".len())).unwrap();
+        assert_eq!((start, end), (3, 3 + "été".len()));
+    }
+
+    #[test]
+    fn test_decode_entities() {
+        assert_eq!(decode_entities("AT&amp;T"), "AT&T");
+        assert_eq!(decode_entities("&#65;&#x42;&lt;"), "AB<");
+        assert_eq!(
+            decode_entities("a & b &unknown; &#xZZ;"),
+            "a & b &unknown; &#xZZ;"
+        );
+    }
+}
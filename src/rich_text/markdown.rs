@@ -0,0 +1,189 @@
+use pulldown_cmark::{Event, Options, Parser, Tag};
+
+use super::{Formatting, Heading, Image, InputBuilder, ListItem, MarkKind};
+use crate::create_tokens::InputToken;
+
+fn remove_mark(formatting: &mut Formatting, mark: MarkKind) {
+    if let Some(idx) = formatting
+        .marks
+        .iter()
+        .rposition(|existing| *existing == mark)
+    {
+        formatting.marks.remove(idx);
+    }
+}
+
+/// Convert Markdown into [InputToken]s, with formatting as [MarkKind], [Link](super::Link),
+/// [Heading] and [ListItem] attributes.
+///
+/// Inline images become custom tokens of size `1` with an [Image] payload.
+/// Blocks like paragraphs and headings are separated by a line break, and other
+/// Markdown syntax (like html or list markers) is left out.
+///
+/// ```
+/// use layered_nlp::{create_line_from_input_tokens, markdown_to_input_tokens, LLLineDisplay, MarkKind};
+///
+/// let ll_line = create_line_from_input_tokens(
+///     markdown_to_input_tokens("Hello **World**!"),
+///     |text| text.len(),
+/// );
+///
+/// assert_eq!(ll_line.query::<MarkKind>()[0].1, "World");
+/// ```
+pub fn markdown_to_input_tokens(markdown: &str) -> Vec<InputToken> {
+    let mut input = InputBuilder::default();
+    let mut formatting = Formatting::default();
+    // whether each of the lists we are in is ordered
+    let mut lists: Vec<bool> = Vec::new();
+    let mut list_items: Vec<ListItem> = Vec::new();
+    // set while inside an image, collecting its alt text
+    let mut image: Option<Image> = None;
+
+    for event in Parser::new_ext(markdown, Options::ENABLE_STRIKETHROUGH) {
+        match event {
+            Event::Start(Tag::Emphasis) => formatting.marks.push(MarkKind::Italic),
+            Event::End(Tag::Emphasis) => remove_mark(&mut formatting, MarkKind::Italic),
+            Event::Start(Tag::Strong) => formatting.marks.push(MarkKind::Bold),
+            Event::End(Tag::Strong) => remove_mark(&mut formatting, MarkKind::Bold),
+            Event::Start(Tag::Strikethrough) => formatting.marks.push(MarkKind::Strikethrough),
+            Event::End(Tag::Strikethrough) => remove_mark(&mut formatting, MarkKind::Strikethrough),
+            Event::Start(Tag::CodeBlock(_)) => {
+                input.push_line_break();
+                formatting.marks.push(MarkKind::Code);
+            }
+            Event::End(Tag::CodeBlock(_)) => remove_mark(&mut formatting, MarkKind::Code),
+            Event::Start(Tag::Heading(level, ..)) => {
+                input.push_line_break();
+                formatting.heading = Some(Heading { level: level as u8 });
+            }
+            Event::End(Tag::Heading(..)) => formatting.heading = None,
+            Event::Start(Tag::List(first_number)) => lists.push(first_number.is_some()),
+            Event::End(Tag::List(_)) => {
+                lists.pop();
+            }
+            Event::Start(Tag::Item) => {
+                input.push_line_break();
+                list_items.push(ListItem {
+                    ordered: lists.last().copied().unwrap_or_default(),
+                });
+                formatting.list_item = list_items.last().copied();
+            }
+            Event::End(Tag::Item) => {
+                list_items.pop();
+                formatting.list_item = list_items.last().copied();
+            }
+            Event::Start(Tag::Link(_, href, title)) => {
+                formatting.link = Some(super::Link {
+                    href: href.to_string(),
+                    title: title.to_string(),
+                });
+            }
+            Event::End(Tag::Link(..)) => formatting.link = None,
+            Event::Start(Tag::Image(_, src, title)) => {
+                image = Some(Image {
+                    src: src.to_string(),
+                    title: title.to_string(),
+                    alt: String::new(),
+                });
+            }
+            Event::End(Tag::Image(..)) => {
+                if let Some(image) = image.take() {
+                    input.push_image(image, &formatting);
+                }
+            }
+            Event::Start(Tag::Paragraph)
+            | Event::Start(Tag::TableRow)
+            | Event::Start(Tag::TableHead)
+            | Event::HardBreak
+            | Event::SoftBreak
+            | Event::Rule => {
+                input.push_line_break();
+            }
+            Event::Text(text) | Event::Code(text) if image.is_some() => {
+                if let Some(image) = &mut image {
+                    image.alt.push_str(&text);
+                }
+            }
+            Event::Text(text) => input.push_text(&text, &formatting),
+            Event::Code(code) => {
+                let mut code_formatting = formatting.clone();
+                code_formatting.marks.push(MarkKind::Code);
+                input.push_text(&code, &code_formatting);
+            }
+            Event::Start(_)
+            | Event::End(_)
+            | Event::Html(_)
+            | Event::FootnoteReference(_)
+            | Event::TaskListMarker(_) => {}
+        }
+    }
+
+    input.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::markdown_to_input_tokens;
+    use crate::create_tokens::create_line_from_input_tokens;
+    use crate::ll_line::{x, LLLineDisplay};
+    use crate::rich_text::{Heading, Image, Link, ListItem, MarkKind};
+
+    #[test]
+    fn test_markdown_to_input_tokens() {
+        let ll_line = create_line_from_input_tokens(
+            markdown_to_input_tokens(
+                "# Hi *there*\n\nSee **[the docs](https://example.com \"Docs\")** and `cargo` ~~now~~\nlater ![logo](logo.png)\n\n- one\n- two",
+            ),
+            |text| text.len(),
+        );
+
+        let mut ll_line_display = LLLineDisplay::new(&ll_line);
+        ll_line_display.include::<MarkKind>();
+        ll_line_display.include::<Link>();
+        ll_line_display.include::<Heading>();
+        ll_line_display.include::<ListItem>();
+
+        insta::assert_snapshot!(ll_line_display, @r###"
+        Hi     there  \n  See     the     docs     and     cargo     now  \n  later     <logo>  \n  one  \n  two
+               ╰───╯Italic
+                                  ╰──────────╯Bold
+                                                           ╰───╯Code
+                                                                     ╰─╯Strikethrough
+                                  ╰──────────╯Link { href: "https://example.com", title: "Docs" }
+        ╰───╯Heading { level: 1 }
+               ╰───╯Heading { level: 1 }
+                                                                                                    ╰─╯ListItem { ordered: false }
+                                                                                                             ╰─╯ListItem { ordered: false }
+        "###);
+
+        let images: Vec<_> = ll_line
+            .find(&x::value::<Image>())
+            .into_iter()
+            .map(|find| *find.attr())
+            .collect();
+        assert_eq!(
+            images,
+            vec![&Image {
+                src: "logo.png".to_string(),
+                title: String::new(),
+                alt: "logo".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_stay_together() {
+        let ll_line = create_line_from_input_tokens(
+            markdown_to_input_tokens("snake\\_case *it\\*s*"),
+            |text| text.len(),
+        );
+
+        let mut ll_line_display = LLLineDisplay::new(&ll_line);
+        ll_line_display.include::<MarkKind>();
+
+        insta::assert_snapshot!(ll_line_display, @r###"
+        snake_case     it  *  s
+                       ╰──────╯Italic
+        "###);
+    }
+}
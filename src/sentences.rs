@@ -0,0 +1,154 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{create_line_from_string, LLLine};
+
+/// Abbreviations which don't end a sentence, used by [SentenceSegmenter::default].
+///
+/// `etc.` is left out since it often ends a sentence.
+const ENGLISH_ABBREVIATIONS: &[&str] = &[
+    "al.", "approx.", "cf.", "co.", "corp.", "dept.", "dr.", "e.g.", "fig.", "i.e.", "inc.", "jr.",
+    "ltd.", "mr.", "mrs.", "ms.", "mt.", "prof.", "sr.", "st.", "vs.",
+];
+
+/// Splits text into sentences with the Unicode sentence boundaries, without splitting after
+/// abbreviations like `Dr.` or `e.g.` and initials like `J.`.
+///
+/// ```
+/// use layered_nlp::SentenceSegmenter;
+///
+/// let segmenter = SentenceSegmenter::default();
+/// assert_eq!(
+///     segmenter.split("Dr. Smith arrived, e.g. Paris. It rained!"),
+///     vec!["Dr. Smith arrived, e.g. Paris.", "It rained!"],
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SentenceSegmenter {
+    /// lowercase, ending with `.`
+    abbreviations: Vec<String>,
+}
+
+impl Default for SentenceSegmenter {
+    /// Segmenter knowing common English abbreviations
+    fn default() -> Self {
+        SentenceSegmenter::new().with_abbreviations(ENGLISH_ABBREVIATIONS.iter().copied())
+    }
+}
+
+impl SentenceSegmenter {
+    /// Segmenter without any abbreviations
+    pub fn new() -> Self {
+        SentenceSegmenter {
+            abbreviations: Vec::new(),
+        }
+    }
+
+    /// Don't end sentences after `abbreviations`, compared case insensitively with or without their final `.`
+    pub fn with_abbreviations<I, S>(mut self, abbreviations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.abbreviations
+            .extend(abbreviations.into_iter().map(|abbreviation| {
+                let abbreviation = abbreviation.as_ref().to_lowercase();
+                if abbreviation.ends_with('.') {
+                    abbreviation
+                } else {
+                    abbreviation + "."
+                }
+            }));
+        self
+    }
+
+    /// Byte ranges (start, end exclusive) of the sentences of `text`, without their surrounding whitespace
+    pub fn sentence_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        let mut continues_sentence = false;
+
+        for (start, sentence) in text.split_sentence_bound_indices() {
+            let trimmed = sentence.trim_start();
+            let start = start + sentence.len() - trimmed.len();
+            let trimmed = trimmed.trim_end();
+            if trimmed.is_empty() {
+                continue;
+            }
+            let end = start + trimmed.len();
+
+            match ranges.last_mut() {
+                Some(last) if continues_sentence => last.1 = end,
+                _ => ranges.push((start, end)),
+            }
+            continues_sentence = self.ends_with_abbreviation(trimmed);
+        }
+
+        ranges
+    }
+
+    /// The sentences of `text`, without their surrounding whitespace
+    pub fn split<'t>(&self, text: &'t str) -> Vec<&'t str> {
+        self.sentence_ranges(text)
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect()
+    }
+
+    /// Create a line for each sentence of `text`, see [create_line_from_string]
+    pub fn create_lines(&self, text: &str) -> Vec<LLLine> {
+        self.split(text)
+            .into_iter()
+            .map(create_line_from_string)
+            .collect()
+    }
+
+    fn ends_with_abbreviation(&self, sentence: &str) -> bool {
+        if !sentence.ends_with('.') {
+            return false;
+        }
+
+        let last_word = sentence
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches(|c: char| !c.is_alphanumeric());
+
+        let mut chars = last_word.chars();
+        let is_initial =
+            matches!((chars.next(), chars.as_str()), (Some(c), ".") if c.is_uppercase());
+
+        is_initial
+            || self
+                .abbreviations
+                .iter()
+                .any(|abbreviation| abbreviation == &last_word.to_lowercase())
+    }
+}
+
+#[test]
+fn test_sentence_ranges() {
+    let segmenter = SentenceSegmenter::default();
+
+    assert_eq!(
+        segmenter.split("  When it rains, then it pours.  Bring an umbrella!\nOk? "),
+        vec!["When it rains, then it pours.", "Bring an umbrella!", "Ok?"]
+    );
+    assert_eq!(
+        segmenter.split("Ask Mr. Smith, i.e. the owner. J. R. R. Tolkien wrote it (approx. 1937)."),
+        vec![
+            "Ask Mr. Smith, i.e. the owner.",
+            "J. R. R. Tolkien wrote it (approx. 1937)."
+        ]
+    );
+    assert_eq!(
+        SentenceSegmenter::new().split("Ask Mr. Smith."),
+        vec!["Ask Mr.", "Smith."]
+    );
+    assert_eq!(
+        SentenceSegmenter::new()
+            .with_abbreviations(["Abbr"])
+            .split("See abbr. Below."),
+        vec!["See abbr. Below."]
+    );
+    assert_eq!(segmenter.sentence_ranges(" \n "), Vec::new());
+    assert_eq!(segmenter.sentence_ranges(" Hi. Yo"), vec![(1, 4), (5, 7)]);
+}
@@ -1,6 +1,10 @@
 mod currency_amount;
+mod line_editor;
 mod ll_selection;
+mod position_lookup;
+mod resolver_ext;
 mod tokenizing;
+mod value_token;
 
 use crate::create_tokens::*;
 use crate::ll_line::{LLCursorAssignment, LLLine, LLLineDisplay, LLSelection, Resolver, TextTag};
@@ -1,4 +1,5 @@
 use rust_decimal::Decimal;
+use std::rc::Rc;
 
 use super::{test_line, LLCursorAssignment, LLLineDisplay, Resolver, TextTag};
 use crate::ll_line::{x, FinishWith, LLSelection};
@@ -28,6 +29,7 @@ impl Resolver for CurrencySymbolResolver {
 }
 
 #[derive(Clone, Debug)]
+#[allow(dead_code)]
 struct Amount(Decimal);
 
 struct AmountResolver {
@@ -107,6 +109,7 @@ impl Resolver for AmountResolver {
 }
 
 #[derive(Debug, Clone)]
+#[allow(dead_code)]
 struct CurrencyAmount(CurrencySymbol, Amount);
 
 struct CurrencyAmountResolver;
@@ -139,7 +142,7 @@ fn it_works_usd() {
     ll_line_display.include::<Amount>();
     ll_line_display.include::<CurrencyAmount>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     $  1  ,  000  .  25
     ╰USDDollars
        ╰──────────────╯Amount(1000.25)
@@ -161,7 +164,7 @@ fn it_works_euro() {
     ll_line_display.include::<Amount>();
     ll_line_display.include::<CurrencyAmount>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     .     1     000  ,  25  €
                             ╰Euro
           ╰──────────────╯Amount(1000.25)
@@ -179,8 +182,141 @@ fn trailing_delimiter() {
     let mut ll_line_display = LLLineDisplay::new(&ll_line);
     ll_line_display.include::<Amount>();
 
-    insta::assert_display_snapshot!(ll_line_display, @r###"
+    insta::assert_snapshot!(ll_line_display, @r###"
     100  ,  .
     ╰─╯Amount(100)
     "###);
 }
+
+#[test]
+fn captures() {
+    let ll_line = Rc::new(
+        test_line("Pay $1,000.25 now")
+            .run(&CurrencySymbolResolver)
+            .run(&AmountResolver {
+                delimiters: vec![','],
+                decimal: '.',
+            }),
+    );
+    let selection = LLSelection::from_line(ll_line.clone()).unwrap();
+
+    let found = selection.find_captures(&x::seq((
+        x::capture("verb", x::token_text()),
+        x::whitespace(),
+        x::capture(
+            "currency_amount",
+            x::seq((
+                x::capture("symbol", x::attr::<CurrencySymbol>()),
+                x::capture("amount", x::attr::<Amount>()),
+            )),
+        ),
+    )));
+    assert_eq!(found.len(), 1);
+    let (sel, captures) = &found[0];
+
+    insta::assert_debug_snapshot!((sel, captures), @r###"
+    (
+        LLSelection {
+            start_idx: 0,
+            end_idx: 7,
+        },
+        {
+            "verb": Some(
+                (
+                    0,
+                    0,
+                ),
+            ),
+            "currency_amount": Some(
+                (
+                    2,
+                    7,
+                ),
+            ),
+            "symbol": Some(
+                (
+                    2,
+                    2,
+                ),
+            ),
+            "amount": Some(
+                (
+                    3,
+                    7,
+                ),
+            ),
+        },
+    )
+    "###);
+    assert_eq!(captures.text("verb"), Some("Pay"));
+    assert!(matches!(
+        captures.get::<CurrencySymbol>("symbol"),
+        Some(CurrencySymbol::USDDollars)
+    ));
+    assert_eq!(
+        captures.get::<Amount>("amount").map(|amount| amount.0),
+        Some(Decimal::new(100025, 2))
+    );
+    assert!(captures.get::<Amount>("symbol").is_none());
+    insta::assert_debug_snapshot!(captures.selection("amount"), @r###"
+    Some(
+        LLSelection {
+            start_idx: 3,
+            end_idx: 7,
+        },
+    )
+    "###);
+
+    // going backwards
+    let (now_sel, _) = selection
+        .find_by(&x::attr_eq(&TextTag::WORD))
+        .pop()
+        .unwrap();
+    let (_, out) = now_sel
+        .match_first_backwards(&x::seq((
+            x::whitespace(),
+            x::capture("amount", x::attr::<Amount>()),
+        )))
+        .unwrap();
+    let mut backwards_captures = x::Captures::new(ll_line);
+    x::CollectCaptures::collect_captures(&out, &mut backwards_captures);
+    assert_eq!(
+        backwards_captures.selection("amount"),
+        captures.selection("amount")
+    );
+}
+
+#[test]
+fn pattern() {
+    let registry = x::PatternRegistry::default()
+        .register::<CurrencySymbol>("CurrencySymbol")
+        .register::<Amount>("Amount");
+    let pattern = x::Pattern::parse("[CurrencySymbol] _? [Amount]", &registry).unwrap();
+
+    let ll_line = test_line("$ 1,000.25 or $20")
+        .run(&CurrencySymbolResolver)
+        .run(&AmountResolver {
+            delimiters: vec![','],
+            decimal: '.',
+        });
+    let selection = LLSelection::from_line(Rc::new(ll_line)).unwrap();
+
+    insta::assert_debug_snapshot!(selection.find_by(&pattern), @r###"
+    [
+        (
+            LLSelection {
+                start_idx: 0,
+                end_idx: 6,
+            },
+            [],
+        ),
+        (
+            LLSelection {
+                start_idx: 10,
+                end_idx: 11,
+            },
+            [],
+        ),
+    ]
+    "###);
+}
@@ -0,0 +1,225 @@
+use super::LLLineDisplay;
+use crate::ll_line::x;
+use crate::{
+    Contraction, ContractionResolver, InputToken, LLCursorAssignment, LLLine, LLSelection, LToken,
+    LineEdit, LineEditError, LineEditor, Pipeline, Resolver, TextMatchAssignResolver, TextTag,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Place {
+    City,
+    Country,
+}
+
+/// "in" followed by a [Place], to check resolvers looking at neighbouring tokens
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+struct InPlace(Place);
+
+struct InPlaceResolver;
+
+impl Resolver for InPlaceResolver {
+    type Attr = InPlace;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::seq((
+                x::token_text(),
+                x::whitespace(),
+                x::attr::<Place>(),
+            )))
+            .into_iter()
+            .filter(|(_, (text, _, _))| text.eq_ignore_ascii_case("in"))
+            .map(|(selection, (_, _, place))| selection.finish_with_attr(InPlace(place.clone())))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Bold;
+
+fn editor() -> LineEditor<fn(&str) -> usize> {
+    LineEditor::new((|text: &str| text.encode_utf16().count()) as fn(&str) -> usize).with_pipeline(
+        Pipeline::new()
+            .with(TextMatchAssignResolver::new_case_insensitive_str_arr([
+                ("Paris", Place::City),
+                ("Lyon", Place::City),
+                ("France", Place::Country),
+            ]))
+            .with(InPlaceResolver)
+            .with(ContractionResolver),
+    )
+}
+
+fn line_text(ll_line: &LLLine) -> String {
+    ll_line
+        .ll_tokens()
+        .iter()
+        .map(|ll_token| match ll_token.get_token() {
+            LToken::Text(text, _) => text.as_str(),
+            LToken::Value(_) => "",
+        })
+        .collect()
+}
+
+/// Attributes in a stable order, since edited lines store new attributes after the moved ones
+fn summary(ll_line: &LLLine) -> Vec<String> {
+    fn query<T: std::fmt::Debug + 'static>(ll_line: &LLLine, summary: &mut Vec<String>) {
+        for (range, text, values) in ll_line.query::<T>() {
+            summary.push(format!("{:?} {:?} {:?}", range, text, values));
+        }
+    }
+
+    let mut summary = Vec::new();
+    query::<TextTag>(ll_line, &mut summary);
+    query::<char>(ll_line, &mut summary);
+    query::<Place>(ll_line, &mut summary);
+    query::<InPlace>(ll_line, &mut summary);
+    query::<Contraction>(ll_line, &mut summary);
+    summary.sort();
+    summary
+}
+
+#[test]
+fn test_line_editor_same_as_new_line() {
+    let editor = editor();
+    let mut ll_line = editor
+        .create_line(vec![InputToken::text(
+            "We don't live in Paris".to_string(),
+            Vec::new(),
+        )])
+        .unwrap();
+
+    for edit in [
+        // "We don't live in Lyon"
+        LineEdit::new(17, 22, "Lyon"),
+        // "We do live in Lyon"
+        LineEdit::delete(5, 8),
+        // "We do live in Lyon, France"
+        LineEdit::insert(18, ", France"),
+        // "We do live inLyon, France"
+        LineEdit::delete(13, 14),
+        // "We do live in Lyon, France"
+        LineEdit::insert(13, " "),
+        // "They'll live in Lyon, France"
+        LineEdit::new(0, 5, "They'll"),
+        // "They'll live in Lyon, France now"
+        LineEdit::insert(28, " now"),
+        // ""
+        LineEdit::delete(0, 32),
+        // "in France"
+        LineEdit::insert(0, "in France"),
+        // "live in Pariss"
+        LineEdit::new(0, 9, "live in Pariss"),
+        // "live in Paris", "in" is outside of the edited tokens
+        LineEdit::delete(13, 14),
+        // "live in Paris a. b"
+        LineEdit::insert(13, " a. b"),
+        // "live in Paris a.b", which is a single word made of tokens around the edit
+        LineEdit::delete(16, 17),
+        // "live in Paris"
+        LineEdit::delete(13, 17),
+    ] {
+        ll_line = editor.apply(ll_line, &edit).unwrap();
+
+        let text = line_text(&ll_line);
+        let expected = if text.is_empty() {
+            editor.create_line(Vec::new()).unwrap()
+        } else {
+            editor
+                .create_line(vec![InputToken::text(text, Vec::new())])
+                .unwrap()
+        };
+
+        assert_eq!(summary(&ll_line), summary(&expected), "after {:?}", edit);
+        assert_eq!(
+            ll_line
+                .find(&x::token_text())
+                .last()
+                .map(|find| find.range()),
+            expected
+                .find(&x::token_text())
+                .last()
+                .map(|find| find.range()),
+        );
+    }
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Place>();
+    ll_line_display.include::<InPlace>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    live     in     Paris
+                    ╰───╯City
+             ╰──────────╯InPlace(City)
+    "###);
+}
+
+#[test]
+fn test_line_editor_moves_attributes() {
+    let editor = editor();
+    let ll_line = editor
+        .create_line(vec![
+            InputToken::text("I don't like".to_string(), Vec::new()),
+            InputToken::custom(1, Vec::new()),
+            InputToken::text(" Lyon".to_string(), vec![crate::AnyAttribute::new(Bold)]),
+        ])
+        .unwrap();
+
+    let ll_line = editor
+        .apply(ll_line, &LineEdit::new(2, 7, "really"))
+        .unwrap();
+    // right before the custom token
+    let ll_line = editor.apply(ll_line, &LineEdit::insert(13, "d")).unwrap();
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Bold>();
+    ll_line_display.include::<Place>();
+    ll_line_display.include::<Contraction>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    I     really     liked  <>     Lyon
+                                ╰─────╯Bold
+                                   ╰──╯City
+    "###);
+}
+
+#[test]
+fn test_line_editor_errors() {
+    let editor = editor();
+    let ll_line = || {
+        editor
+            .create_line(vec![
+                InputToken::text("Paris 🇫🇷".to_string(), Vec::new()),
+                InputToken::custom(2, Vec::new()),
+            ])
+            .unwrap()
+    };
+
+    let apply = |edit: LineEdit| editor.apply(ll_line(), &edit).err();
+
+    assert_eq!(
+        apply(LineEdit::delete(10, 13)),
+        Some(LineEditError::OutOfBounds {
+            start: 10,
+            end: 13,
+            line_end: 12
+        })
+    );
+    assert_eq!(
+        apply(LineEdit::insert(11, "x")),
+        Some(LineEditError::SplitsCustomToken { token_idx: 3 })
+    );
+    assert_eq!(
+        apply(LineEdit::delete(7, 8)),
+        Some(LineEditError::NotACharBoundary {
+            position: 7,
+            token_idx: 2
+        })
+    );
+
+    // whole custom tokens can be removed
+    let ll_line = editor.apply(ll_line(), &LineEdit::delete(6, 12)).unwrap();
+    assert_eq!(ll_line.ll_tokens().len(), 2);
+    assert_eq!(line_text(&ll_line), "Paris ");
+}
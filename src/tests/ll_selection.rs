@@ -9,12 +9,12 @@ fn find_by() {
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰"here"
                                      ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                     ╰"here"
                        ╰"here"
@@ -22,19 +22,19 @@ fn find_by() {
                                            ╰"here"
                                               ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰"here"
                        ╰"here"
                                         ╰"here"
                                                            ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰"here"
            ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -51,23 +51,23 @@ fn find_first_by() {
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -86,29 +86,29 @@ fn split_by() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
     ╰────────────╯"here"
                        ╰──────────╯"here"
                                         ╰────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
     ╰────────────╯"here"
                              ╰──────────╯"here"
                                                  ╰────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
        ╰────────────╯"here"
                           ╰──────────╯"here"
                                            ╰────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
        ╰╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -122,27 +122,27 @@ fn find_by_seq() {
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰────╯"here"
                                      ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                           ╰────╯"here"
                                               ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰─────╯"here"
                        ╰────╯"here"
                                         ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰───╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -154,35 +154,33 @@ fn match_seq_forwards() {
         range_sel
             .find_by(&x::attr::<TextTag>())
             .into_iter()
-            .map(|(sel, _)| {
+            .flat_map(|(sel, _)| {
                 sel.match_forwards(&x::seq((
                     x::attr_eq(&TextTag::NATN),
                     x::attr_eq(&TextTag::WORD),
                 )))
             })
-            .into_iter()
-            .flatten()
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰────────╯"here"
                                      ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                           ╰────────╯"here"
                                               ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰─────────╯"here"
                        ╰────────╯"here"
                                         ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -194,35 +192,33 @@ fn match_seq_backwards() {
         range_sel
             .find_by(&x::attr::<TextTag>())
             .into_iter()
-            .map(|(sel, _)| {
+            .flat_map(|(sel, _)| {
                 sel.match_backwards(&x::seq((
                     x::attr_eq(&TextTag::NATN),
                     x::attr_eq(&TextTag::WORD),
                 )))
             })
-            .into_iter()
-            .flatten()
             .finish_with(|_| String::from("here"))
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
           ╰─────────╯"here"
                             ╰────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
           ╰─────────╯"here"
                                   ╰────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
              ╰─────────╯"here"
                                ╰────────╯"here"
                                                  ╰─────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -235,32 +231,31 @@ fn one_of() {
             .find_by(&x::attr_eq(&TextTag::NATN))
             .into_iter()
             .flat_map(|(sel, _)| Some(sel.match_first_forwards(&x::token_has_any(&['(', '.']))?.0))
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
               ╰─────╯"here"
                                 ╰────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
               ╰─────╯"here"
                                       ╰────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
                  ╰─────╯"here"
                                    ╰────╯"here"
                                                      ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
        ╰───╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -273,32 +268,31 @@ fn one_of_backwards() {
             .find_by(&x::attr_eq(&TextTag::NATN))
             .into_iter()
             .flat_map(|(sel, _)| Some(sel.match_first_backwards(&x::token_has_any(&['(', '.']))?.0))
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
                     ╰────╯"here"
                                      ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                           ╰────╯"here"
                                               ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰─────╯"here"
                        ╰────╯"here"
                                         ╰─────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰───╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -319,20 +313,19 @@ fn one_of_seq() {
                     .0,
                 )
             })
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
               ╰────────╯"here"
                                       ╰───────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -353,20 +346,19 @@ fn one_of_seq_backwards() {
                     .0,
                 )
             })
-            .into_iter()
             .map(|sel| sel.finish_with_attr(String::from("here")))
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @"0000  aa  0000  .  000  aa  000  .  0000  aa  0000");
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
                        ╰───────╯"here"
                                            ╰────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @".");
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @".  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .");
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @".  00  .");
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @".");
 }
 
 #[test]
@@ -382,23 +374,23 @@ fn trim_start() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
           ╰──────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
           ╰───────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰──────────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰──────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -417,23 +409,23 @@ fn trim_end() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
     ╰──────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
     ╰───────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰──────────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰──────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
@@ -452,24 +444,394 @@ fn trim() {
             .collect()
     };
 
-    insta::assert_display_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000.000aa000.0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  000  aa  000  .  0000  aa  0000
           ╰────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver("0000aa0000...000aa000..0000aa0000", split_by_char), @r###"
     0000  aa  0000  .  .  .  000  aa  000  .  .  0000  aa  0000
           ╰─────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".0000aa0000.000aa000.0000aa0000.", split_by_char), @r###"
     .  0000  aa  0000  .  000  aa  000  .  0000  aa  0000  .
     ╰──────────────────────────────────────────────────────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".00.", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".00.", split_by_char), @r###"
     .  00  .
     ╰──────╯"here"
     "###);
-    insta::assert_display_snapshot!(test_resolver(".", split_by_char), @r###"
+    insta::assert_snapshot!(test_resolver(".", split_by_char), @r###"
     .
     ╰"here"
     "###);
 }
+
+#[test]
+fn find_by_seq_ws() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let symbol_then_number = |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::seq_ws((
+                x::attr_eq(&TextTag::SYMB),
+                x::attr_eq(&TextTag::NATN),
+            )))
+            .finish_with(|_| String::from("here"))
+    };
+
+    insta::assert_snapshot!(test_resolver("$100 or $ 100 or $   100 or $ a100", symbol_then_number), @r###"
+    $  100     or     $     100     or     $       100     or     $     a100
+    ╰────╯"here"
+                      ╰───────╯"here"
+                                           ╰─────────╯"here"
+    "###);
+}
+
+#[test]
+fn match_seq_ws_backwards() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let number_before_word = |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::attr_eq(&TextTag::PUNC))
+            .into_iter()
+            .flat_map(|(sel, _)| {
+                sel.match_backwards(&x::seq_skip(
+                    (x::attr_eq(&TextTag::WORD), x::attr_eq(&TextTag::NATN)),
+                    x::any_of((x::whitespace(), x::attr_eq(&TextTag::SYMB))),
+                ))
+            })
+            .finish_with(|_| String::from("here"))
+    };
+
+    insta::assert_snapshot!(test_resolver("100 apples. 100 $ apples. 100apples.", number_before_word), @r###"
+    100     apples  .     100     $     apples  .     100  apples  .
+    ╰───────────────╯"here"
+                          ╰─────────────────────╯"here"
+                                                      ╰────────────╯"here"
+    "###);
+}
+
+#[test]
+fn find_by_line_anchors() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let first_and_last_number = |range_sel: LLSelection| {
+        let first = range_sel
+            .find_by(&x::seq((x::line_start(), x::attr_eq(&TextTag::NATN))))
+            .finish_with(|_| String::from("first"));
+        let last = range_sel
+            .find_by(&x::seq((x::attr_eq(&TextTag::NATN), x::line_end())))
+            .finish_with(|_| String::from("last"));
+        first.into_iter().chain(last).collect()
+    };
+
+    insta::assert_snapshot!(test_resolver("00aa00.00", first_and_last_number), @r###"
+    00  aa  00  .  00
+    ╰╯"first"
+                   ╰╯"last"
+    "###);
+    insta::assert_snapshot!(test_resolver(".00aa00.00.", first_and_last_number), @".  00  aa  00  .  00  .");
+    // zero-width matches alone don't select anything
+    insta::assert_snapshot!(test_resolver("00", |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::line_start())
+            .finish_with(|_| String::from("here"))
+    }), @"00");
+}
+
+#[test]
+fn match_line_start_backwards() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let number_at_line_start = |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::attr_eq(&TextTag::PUNC))
+            .into_iter()
+            .flat_map(|(sel, _)| {
+                sel.match_backwards(&x::seq((x::attr_eq(&TextTag::NATN), x::line_start())))
+            })
+            .finish_with(|_| String::from("here"))
+    };
+
+    insta::assert_snapshot!(test_resolver("00.aa00.", number_at_line_start), @r###"
+    00  .  aa00  .
+    ╰───╯"here"
+    "###);
+}
+
+#[test]
+fn find_by_selection_anchors() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let numbers_around_selection = |range_sel: LLSelection| {
+        range_sel
+            .split_by(&x::attr_eq(&TextTag::PUNC))
+            .into_iter()
+            .flat_map(|sel| {
+                let first = sel
+                    .find_by(&x::seq((x::selection_start(), x::attr_eq(&TextTag::NATN))))
+                    .finish_with(|_| String::from("first"));
+                let last = sel
+                    .find_by(&x::seq((x::attr_eq(&TextTag::NATN), x::selection_end())))
+                    .finish_with(|_| String::from("last"));
+                first.into_iter().chain(last).collect::<Vec<_>>()
+            })
+            .collect()
+    };
+
+    insta::assert_snapshot!(test_resolver("00aa00.aa00aa.00", numbers_around_selection), @r###"
+    00  aa  00  .  aa00aa  .  00
+    ╰╯"first"
+            ╰╯"last"
+                              ╰╯"last"
+                              ╰╯"first"
+    "###);
+}
+
+#[test]
+fn contains_in_any_order() {
+    use crate::ll_line::{x, LLSelection, TextTag};
+    use crate::TextMatchAssignResolver;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Person(&'static str);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Pays;
+
+    let ll_line = crate::tests::test_line("Alice pays Bob 100, and 20 is paid to Dave by Carol")
+        .run(&TextMatchAssignResolver::new_case_insensitive_str_arr([
+            ("alice", Person("Alice")),
+            ("bob", Person("Bob")),
+            ("carol", Person("Carol")),
+            ("dave", Person("Dave")),
+        ]))
+        .run(&TextMatchAssignResolver::new_case_insensitive_str_arr([
+            ("pays", Pays),
+            ("paid", Pays),
+        ]));
+    let selection = LLSelection::from_line(std::rc::Rc::new(ll_line)).unwrap();
+
+    let clauses = selection.split_by(&x::attr_eq(&TextTag::PUNC));
+    let slots: Vec<_> = clauses
+        .iter()
+        .flat_map(|clause| {
+            clause.contains_in_any_order(&(
+                x::multiple::<Person, 2>(),
+                x::attr_eq(&Pays),
+                x::all((x::attr_eq(&TextTag::NATN), x::token_text())),
+            ))
+        })
+        .map(|(_, (people, (), (_, amount)))| (people, amount))
+        .collect();
+
+    insta::assert_debug_snapshot!(slots, @r###"
+    [
+        (
+            [
+                Person(
+                    "Alice",
+                ),
+                Person(
+                    "Bob",
+                ),
+            ],
+            "100",
+        ),
+        (
+            [
+                Person(
+                    "Dave",
+                ),
+                Person(
+                    "Carol",
+                ),
+            ],
+            "20",
+        ),
+    ]
+    "###);
+
+    // a token can't fill two slots
+    assert_eq!(
+        selection
+            .contains_in_any_order(&(
+                x::multiple_of::<2, _>(x::attr_eq(&Pays)),
+                x::multiple::<Person, 4>(),
+            ))
+            .len(),
+        1
+    );
+    assert!(selection
+        .contains_in_any_order(&(x::multiple::<Person, 4>(), x::attr::<Person>()))
+        .is_empty());
+}
+
+#[test]
+fn find_by_then_within() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    let number_then_word = |max_tokens: usize| {
+        move |range_sel: LLSelection| {
+            range_sel
+                .find_by(&x::then_within(
+                    x::attr_eq(&TextTag::NATN),
+                    x::attr_eq(&TextTag::WORD),
+                    max_tokens,
+                ))
+                .finish_with(|_| String::from("here"))
+        }
+    };
+
+    insta::assert_snapshot!(test_resolver("10 aa 20 ,bb", number_then_word(1)), @r###"
+    10     aa     20     ,  bb
+    ╰───────╯"here"
+    "###);
+    insta::assert_snapshot!(test_resolver("10 aa 20 ,bb", number_then_word(2)), @r###"
+    10     aa     20     ,  bb
+    ╰───────╯"here"
+                  ╰──────────╯"here"
+    "###);
+}
+
+#[test]
+fn find_by_then_anywhere() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    insta::assert_snapshot!(test_resolver("10 aa 20 ,bb", |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::then_anywhere(
+                x::attr_eq(&TextTag::NATN),
+                x::attr_eq(&TextTag::WORD),
+            ))
+            .finish_with(|_| String::from("here"))
+    }), @r###"
+    10     aa     20     ,  bb
+    ╰───────╯"here"
+    ╰────────────────────────╯"here"
+                  ╰──────────╯"here"
+    "###);
+    insta::assert_snapshot!(test_resolver("10 aa 20 ,bb", |range_sel: LLSelection| {
+        range_sel
+            .find_by(
+                &x::then_anywhere(x::attr_eq(&TextTag::NATN), x::attr_eq(&TextTag::WORD))
+                    .not_crossing(x::attr_eq(&TextTag::PUNC)),
+            )
+            .finish_with(|_| String::from("here"))
+    }), @r###"
+    10     aa     20     ,  bb
+    ╰───────╯"here"
+    "###);
+}
+
+#[test]
+fn find_by_then_anywhere_in_clause() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    // the word right after the first clause isn't part of it
+    insta::assert_snapshot!(test_resolver("10,bb 20 cc", |range_sel: LLSelection| {
+        range_sel
+            .split_by(&x::attr_eq(&TextTag::PUNC))
+            .into_iter()
+            .flat_map(|clause| {
+                clause.find_by(&x::then_anywhere(
+                    x::attr_eq(&TextTag::NATN),
+                    x::attr_eq(&TextTag::WORD),
+                ))
+            })
+            .finish_with(|_| String::from("here"))
+    }), @r###"
+    10  ,  bb     20     cc
+                  ╰───────╯"here"
+    "###);
+}
+
+#[test]
+fn match_then_within_backwards() {
+    use crate::ll_line::{x, FinishWith, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    insta::assert_snapshot!(test_resolver("10 aa 20 ,bb", |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::attr_eq(&TextTag::WORD))
+            .into_iter()
+            .flat_map(|(sel, _)| {
+                sel.match_backwards(&x::then_within(
+                    x::attr_eq(&TextTag::PUNC),
+                    x::attr_eq(&TextTag::NATN),
+                    1,
+                ))
+            })
+            .finish_with(|_| String::from("here"))
+    }), @r###"
+    10     aa     20     ,  bb
+                  ╰──────────╯"here"
+    "###);
+}
+
+#[test]
+fn find_by_seq_spans() {
+    use crate::ll_line::{x, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    insta::assert_snapshot!(test_resolver("aa 10 bb20", |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::seq_spans((
+                x::attr_eq(&TextTag::WORD),
+                x::whitespace(),
+                x::attr_eq(&TextTag::NATN),
+            )))
+            .into_iter()
+            .flat_map(|(sel, (word, space, number))| {
+                vec![
+                    word.selection(&sel).unwrap().finish_with_attr(String::from("word")),
+                    space.selection(&sel).unwrap().finish_with_attr(String::from("space")),
+                    number.selection(&sel).unwrap().finish_with_attr(String::from("number")),
+                ]
+            })
+            .collect()
+    }), @r###"
+    aa     10     bb20
+    ╰╯"word"
+        ╰"space"
+           ╰╯"number"
+    "###);
+}
+
+#[test]
+fn match_seq_spans_backwards() {
+    use crate::ll_line::{x, LLSelection, TextTag};
+    use crate::tests::test_resolver;
+
+    insta::assert_snapshot!(test_resolver("aa 10 bb20", |range_sel: LLSelection| {
+        range_sel
+            .find_by(&x::attr_eq(&TextTag::NATN))
+            .into_iter()
+            .flat_map(|(sel, _)| {
+                sel.match_backwards(&x::seq_spans((
+                    x::whitespace(),
+                    x::attr_eq(&TextTag::WORD),
+                )))
+            })
+            .flat_map(|(sel, (space, word))| {
+                vec![
+                    space.selection(&sel).unwrap().finish_with_attr(String::from("space")),
+                    word.selection(&sel).unwrap().finish_with_attr(String::from("word")),
+                ]
+            })
+            .collect()
+    }), @r###"
+    aa     10     bb20
+        ╰"space"
+    ╰╯"word"
+    "###);
+}
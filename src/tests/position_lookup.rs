@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use super::test_line;
+use crate::ll_line::{x, PositionUnit};
+use crate::{LLSelection, LToken, TextMatchAssignResolver};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Place {
+    City,
+    Country,
+}
+
+fn places_line() -> crate::LLLine {
+    test_line("Paris, France is nice").run(&TextMatchAssignResolver::new_case_insensitive_str_arr(
+        [("Paris", Place::City), ("France", Place::Country)],
+    ))
+}
+
+#[test]
+fn test_token_at_position() {
+    let ll_line = places_line();
+
+    let token_text = |pos: usize| {
+        ll_line
+            .token_at_position(pos)
+            .map(|ll_token| match ll_token.get_token() {
+                LToken::Text(text, _) => text.as_str(),
+                LToken::Value(_) => "",
+            })
+    };
+
+    assert_eq!(token_text(0), Some("Paris"));
+    assert_eq!(token_text(4), Some("Paris"));
+    assert_eq!(token_text(5), Some(","));
+    assert_eq!(token_text(8), Some("France"));
+    assert_eq!(token_text(20), Some("nice"));
+    assert_eq!(token_text(21), None);
+}
+
+#[test]
+fn test_attrs_covering_position() {
+    let ll_line = places_line();
+
+    let covering = ll_line.attrs_covering_position::<Place>(9);
+    assert_eq!(covering.len(), 1);
+    assert_eq!(covering[0].attr(), &&Place::Country);
+    assert_eq!(covering[0].range(), (7, 13));
+    assert_eq!(covering[0].range_in(PositionUnit::Byte), (7, 13));
+
+    assert!(ll_line.attrs_covering_position::<Place>(5).is_empty());
+    assert!(ll_line.attrs_covering_position::<Place>(100).is_empty());
+}
+
+#[test]
+fn test_selection_from_position_range() {
+    let ll_line = Rc::new(places_line());
+
+    let selected = |start: usize, end: usize| {
+        LLSelection::from_position_range(ll_line.clone(), start, end)
+            .map(|selection| format!("{:?}", selection))
+    };
+
+    insta::assert_snapshot!(format!("{:#?}", [
+        selected(0, 0),
+        selected(3, 9),
+        selected(5, 6),
+        selected(13, 21),
+        selected(21, 25),
+        selected(9, 3),
+    ]), @r###"
+    [
+        Some(
+            "LLSelection { start_idx: 0, end_idx: 0 }",
+        ),
+        Some(
+            "LLSelection { start_idx: 0, end_idx: 3 }",
+        ),
+        Some(
+            "LLSelection { start_idx: 1, end_idx: 1 }",
+        ),
+        Some(
+            "LLSelection { start_idx: 4, end_idx: 7 }",
+        ),
+        None,
+        None,
+    ]
+    "###);
+
+    // the line returned by `run` doesn't need to be wrapped in an `Rc`
+    let selection = LLSelection::from_position_range(places_line(), 8, 9).unwrap();
+    assert_eq!(
+        selection
+            .find_by(&x::attr::<Place>())
+            .into_iter()
+            .map(|(_, place)| place.clone())
+            .collect::<Vec<_>>(),
+        vec![Place::Country]
+    );
+}
@@ -0,0 +1,145 @@
+use super::{test_line, LLLineDisplay, Resolver, TextTag};
+use crate::ll_line::{x, FinishWith, LLCursorAssignment, LLSelection};
+use crate::{ResolverExt, TextMatchAssignResolver};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Service {
+    Slack,
+    Algolia,
+}
+
+fn services() -> TextMatchAssignResolver<Service> {
+    TextMatchAssignResolver::new_case_insensitive_str_arr([
+        ("Slack", Service::Slack),
+        ("Algolia", Service::Algolia),
+    ])
+}
+
+#[derive(Debug, Clone)]
+struct Clause;
+
+/// Tokens between punctuation
+struct ClauseResolver;
+
+impl Resolver for ClauseResolver {
+    type Attr = Clause;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .split_by(&x::attr_eq(&TextTag::PUNC))
+            .into_iter()
+            .filter_map(|clause| clause.trim(&x::attr_eq(&TextTag::SPACE)))
+            .map(|clause| clause.finish_with_attr(Clause))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FirstWord;
+
+/// First word of the selection
+struct FirstWordResolver;
+
+impl Resolver for FirstWordResolver {
+    type Attr = FirstWord;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_first_by(&x::attr_eq(&TextTag::WORD))
+            .into_iter()
+            .finish_with(|_| FirstWord)
+    }
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct ServiceName(String);
+
+#[test]
+fn map_and_filter() {
+    let resolver = services()
+        .filter(|_, service| *service != Service::Algolia)
+        .map(|service| ServiceName(format!("{:?}", service).to_uppercase()));
+    let ll_line = test_line("Slack or Algolia, then slack").run(&resolver);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<ServiceName>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    Slack     or     Algolia  ,     then     slack
+    ╰───╯ServiceName("SLACK")
+                                             ╰───╯ServiceName("SLACK")
+    "###);
+}
+
+#[test]
+fn restrict_to() {
+    let ll_line = test_line("When Slack hears a message, search Algolia")
+        .run(&ClauseResolver)
+        .run(&FirstWordResolver.restrict_to::<Clause>());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Clause>();
+    ll_line_display.include::<FirstWord>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    When     Slack     hears     a     message  ,     search     Algolia
+    ╰────────────────────────────────────────╯Clause
+                                                      ╰────────────────╯Clause
+    ╰──╯FirstWord
+                                                      ╰────╯FirstWord
+    "###);
+}
+
+#[test]
+fn restrict_to_overlapping() {
+    // the same clauses twice, and services inside them
+    let ll_line = test_line("When Slack hears a message, search Algolia")
+        .run(&ClauseResolver)
+        .run(&ClauseResolver)
+        .run(&services().map(|_| Clause))
+        .run(&FirstWordResolver.restrict_to::<Clause>());
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<FirstWord>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    When     Slack     hears     a     message  ,     search     Algolia
+    ╰──╯FirstWord
+                                                      ╰────╯FirstWord
+    "###);
+}
+
+#[test]
+fn and_then() {
+    #[derive(Debug)]
+    #[allow(dead_code)]
+    struct ServiceAction {
+        service: Service,
+        action: String,
+    }
+
+    // extend each service to the word following it
+    let ll_line = test_line("When Slack hears a message, search Algolia").run(
+        &services().and_then(|selection, service| {
+            selection
+                .match_first_forwards(&x::seq((
+                    x::attr_eq(&TextTag::SPACE),
+                    x::all((x::attr_eq(&TextTag::WORD), x::token_text())),
+                )))
+                .into_iter()
+                .finish_with(|(_, (_, action))| ServiceAction {
+                    service: service.clone(),
+                    action: action.to_string(),
+                })
+        }),
+    );
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<ServiceAction>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    When     Slack     hears     a     message  ,     search     Algolia
+             ╰─────────────╯ServiceAction { service: Slack, action: "hears" }
+    "###);
+}
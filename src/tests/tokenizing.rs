@@ -1,4 +1,5 @@
 use super::*;
+use crate::type_bucket::AnyAttribute;
 
 fn split_input(input: &str) -> String {
     format!("{:#?}", test_line(input).ll_tokens())
@@ -8,12 +9,24 @@ fn split_input(input: &str) -> String {
 fn test_tokenizing() {
     let input = ". 1 000.23. € .5";
 
-    insta::assert_display_snapshot!(split_input(input), @r###"
+    insta::assert_snapshot!(split_input(input), @r###"
     [
         LLToken {
             token_idx: 0,
             pos_starts_at: 0,
             pos_ends_at: 1,
+            text_starts_at: TextPositions {
+                byte: 0,
+                char: 0,
+                utf16: 0,
+                grapheme: 0,
+            },
+            text_ends_at: TextPositions {
+                byte: 1,
+                char: 1,
+                utf16: 1,
+                grapheme: 1,
+            },
             token: Text(
                 ".",
                 PUNC,
@@ -23,6 +36,18 @@ fn test_tokenizing() {
             token_idx: 1,
             pos_starts_at: 1,
             pos_ends_at: 2,
+            text_starts_at: TextPositions {
+                byte: 1,
+                char: 1,
+                utf16: 1,
+                grapheme: 1,
+            },
+            text_ends_at: TextPositions {
+                byte: 2,
+                char: 2,
+                utf16: 2,
+                grapheme: 2,
+            },
             token: Text(
                 " ",
                 SPACE,
@@ -32,6 +57,18 @@ fn test_tokenizing() {
             token_idx: 2,
             pos_starts_at: 2,
             pos_ends_at: 3,
+            text_starts_at: TextPositions {
+                byte: 2,
+                char: 2,
+                utf16: 2,
+                grapheme: 2,
+            },
+            text_ends_at: TextPositions {
+                byte: 3,
+                char: 3,
+                utf16: 3,
+                grapheme: 3,
+            },
             token: Text(
                 "1",
                 NATN,
@@ -41,6 +78,18 @@ fn test_tokenizing() {
             token_idx: 3,
             pos_starts_at: 3,
             pos_ends_at: 4,
+            text_starts_at: TextPositions {
+                byte: 3,
+                char: 3,
+                utf16: 3,
+                grapheme: 3,
+            },
+            text_ends_at: TextPositions {
+                byte: 4,
+                char: 4,
+                utf16: 4,
+                grapheme: 4,
+            },
             token: Text(
                 " ",
                 SPACE,
@@ -50,6 +99,18 @@ fn test_tokenizing() {
             token_idx: 4,
             pos_starts_at: 4,
             pos_ends_at: 7,
+            text_starts_at: TextPositions {
+                byte: 4,
+                char: 4,
+                utf16: 4,
+                grapheme: 4,
+            },
+            text_ends_at: TextPositions {
+                byte: 7,
+                char: 7,
+                utf16: 7,
+                grapheme: 7,
+            },
             token: Text(
                 "000",
                 NATN,
@@ -59,6 +120,18 @@ fn test_tokenizing() {
             token_idx: 5,
             pos_starts_at: 7,
             pos_ends_at: 8,
+            text_starts_at: TextPositions {
+                byte: 7,
+                char: 7,
+                utf16: 7,
+                grapheme: 7,
+            },
+            text_ends_at: TextPositions {
+                byte: 8,
+                char: 8,
+                utf16: 8,
+                grapheme: 8,
+            },
             token: Text(
                 ".",
                 PUNC,
@@ -68,6 +141,18 @@ fn test_tokenizing() {
             token_idx: 6,
             pos_starts_at: 8,
             pos_ends_at: 10,
+            text_starts_at: TextPositions {
+                byte: 8,
+                char: 8,
+                utf16: 8,
+                grapheme: 8,
+            },
+            text_ends_at: TextPositions {
+                byte: 10,
+                char: 10,
+                utf16: 10,
+                grapheme: 10,
+            },
             token: Text(
                 "23",
                 NATN,
@@ -77,6 +162,18 @@ fn test_tokenizing() {
             token_idx: 7,
             pos_starts_at: 10,
             pos_ends_at: 11,
+            text_starts_at: TextPositions {
+                byte: 10,
+                char: 10,
+                utf16: 10,
+                grapheme: 10,
+            },
+            text_ends_at: TextPositions {
+                byte: 11,
+                char: 11,
+                utf16: 11,
+                grapheme: 11,
+            },
             token: Text(
                 ".",
                 PUNC,
@@ -86,6 +183,18 @@ fn test_tokenizing() {
             token_idx: 8,
             pos_starts_at: 11,
             pos_ends_at: 12,
+            text_starts_at: TextPositions {
+                byte: 11,
+                char: 11,
+                utf16: 11,
+                grapheme: 11,
+            },
+            text_ends_at: TextPositions {
+                byte: 12,
+                char: 12,
+                utf16: 12,
+                grapheme: 12,
+            },
             token: Text(
                 " ",
                 SPACE,
@@ -95,6 +204,18 @@ fn test_tokenizing() {
             token_idx: 9,
             pos_starts_at: 12,
             pos_ends_at: 13,
+            text_starts_at: TextPositions {
+                byte: 12,
+                char: 12,
+                utf16: 12,
+                grapheme: 12,
+            },
+            text_ends_at: TextPositions {
+                byte: 15,
+                char: 13,
+                utf16: 13,
+                grapheme: 13,
+            },
             token: Text(
                 "€",
                 SYMB,
@@ -104,6 +225,18 @@ fn test_tokenizing() {
             token_idx: 10,
             pos_starts_at: 13,
             pos_ends_at: 14,
+            text_starts_at: TextPositions {
+                byte: 15,
+                char: 13,
+                utf16: 13,
+                grapheme: 13,
+            },
+            text_ends_at: TextPositions {
+                byte: 16,
+                char: 14,
+                utf16: 14,
+                grapheme: 14,
+            },
             token: Text(
                 " ",
                 SPACE,
@@ -113,6 +246,18 @@ fn test_tokenizing() {
             token_idx: 11,
             pos_starts_at: 14,
             pos_ends_at: 15,
+            text_starts_at: TextPositions {
+                byte: 16,
+                char: 14,
+                utf16: 14,
+                grapheme: 14,
+            },
+            text_ends_at: TextPositions {
+                byte: 17,
+                char: 15,
+                utf16: 15,
+                grapheme: 15,
+            },
             token: Text(
                 ".",
                 PUNC,
@@ -122,6 +267,18 @@ fn test_tokenizing() {
             token_idx: 12,
             pos_starts_at: 15,
             pos_ends_at: 16,
+            text_starts_at: TextPositions {
+                byte: 17,
+                char: 15,
+                utf16: 15,
+                grapheme: 15,
+            },
+            text_ends_at: TextPositions {
+                byte: 18,
+                char: 16,
+                utf16: 16,
+                grapheme: 16,
+            },
             token: Text(
                 "5",
                 NATN,
@@ -130,3 +287,227 @@ fn test_tokenizing() {
     ]
     "###);
 }
+
+#[test]
+fn test_tokenizing_non_ascii_digits() {
+    let ll_line = test_line("١٢٣,٤٥ ४२ ２０２１年");
+
+    insta::assert_snapshot!(LLLineDisplay::new(&ll_line).with::<TextTag>(), @r###"
+    ١٢٣  ,  ٤٥     ४२     ２０２１  年
+    ╰─╯NATN
+         ╰PUNC
+            ╰╯NATN
+                ╰SPACE
+                   ╰╯NATN
+                       ╰SPACE
+                          ╰──────╯NATN
+                                    ╰╯WORD
+    "###);
+}
+
+fn tokenized_line<T: crate::Tokenizer>(input: &str, tokenizer: &T) -> String {
+    let ll_line = create_line_from_input_tokens_with_tokenizer(
+        vec![InputToken::text(input.to_string(), Vec::new())],
+        tokenizer,
+        |text| text.encode_utf16().count(),
+    );
+
+    format!("{}", LLLineDisplay::new(&ll_line).with::<TextTag>())
+}
+
+#[test]
+fn test_whitespace_tokenizer() {
+    insta::assert_snapshot!(tokenized_line("let x = a::b(10);\t// ok", &crate::WhitespaceTokenizer::default()), @r###"
+    let     x     =     a::b(10);  	  //     ok
+    ╰─╯WORD
+         ╰SPACE
+            ╰WORD
+               ╰SPACE
+                  ╰SYMB
+                     ╰SPACE
+                        ╰───────╯SYMB
+                                   ╰SPACE
+                                      ╰╯SYMB
+                                          ╰SPACE
+                                             ╰╯WORD
+    "###);
+}
+
+#[test]
+fn test_rule_tokenizer() {
+    let tokenizer = crate::RuleTokenizer::new()
+        .prefixed('@', TextTag::WORD)
+        .keep_together(&["::", "->"], TextTag::SYMB)
+        .char_run(|c| ('\u{4E00}'..='\u{9FFF}').contains(&c), TextTag::WORD);
+
+    insta::assert_snapshot!(tokenized_line("@cole-l: a::b -> 東京都に name@example.com", &tokenizer), @r###"
+    @cole-l  :     a  ::  b     ->     東京都  に     name  @  example.com
+    ╰─────╯WORD
+             ╰PUNC
+                ╰SPACE
+                   ╰WORD
+                      ╰╯SYMB
+                          ╰WORD
+                             ╰SPACE
+                                ╰╯SYMB
+                                    ╰SPACE
+                                       ╰────╯WORD
+                                               ╰╯WORD
+                                                   ╰SPACE
+                                                      ╰──╯WORD
+                                                            ╰SYMB
+                                                               ╰─────────╯SYMB
+    "###);
+}
+
+#[test]
+fn test_tokenizer_not_covering_text() {
+    struct FirstCharTokenizer;
+
+    impl crate::Tokenizer for FirstCharTokenizer {
+        fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+            vec![(&text[..1], TextTag::SYMB)]
+        }
+    }
+
+    let err = try_create_line_from_input_tokens_with_tokenizer(
+        vec![InputToken::text(String::from("abc"), Vec::new())],
+        &FirstCharTokenizer,
+        |text| text.len(),
+    )
+    .err();
+
+    assert_eq!(
+        err,
+        Some(CreateLineError::TokenizerMismatch {
+            input_idx: 0,
+            offset: 1
+        })
+    );
+}
+
+#[test]
+fn test_tokenizer_reordering_text() {
+    struct ReversedTokenizer;
+
+    impl crate::Tokenizer for ReversedTokenizer {
+        fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+            text.split(' ')
+                .rev()
+                .map(|word| (word, TextTag::WORD))
+                .collect()
+        }
+    }
+
+    let tokens = || vec![InputToken::text(String::from("ab cd"), Vec::new())];
+    let err =
+        try_create_line_from_input_tokens_with_tokenizer(tokens(), &ReversedTokenizer, |text| {
+            text.len()
+        })
+        .err();
+    assert_eq!(
+        err,
+        Some(CreateLineError::TokenizerMismatch {
+            input_idx: 0,
+            offset: 0
+        })
+    );
+
+    struct OtherTextTokenizer;
+
+    impl crate::Tokenizer for OtherTextTokenizer {
+        fn tokenize<'t>(&self, _: &'t str) -> Vec<(&'t str, TextTag)> {
+            vec![("xy", TextTag::WORD), (" zw", TextTag::WORD)]
+        }
+    }
+
+    let err =
+        try_create_line_from_input_tokens_with_tokenizer(tokens(), &OtherTextTokenizer, |text| {
+            text.len()
+        })
+        .err();
+    assert_eq!(
+        err,
+        Some(CreateLineError::TokenizerMismatch {
+            input_idx: 0,
+            offset: 0
+        })
+    );
+}
+
+#[test]
+fn test_tokenizing_chat_categories() {
+    let tokenized = tokenized_line(
+        "Hey @cole, see https://example.com/a?b=1 👍🏽\nmail name@example.com in #general!",
+        &crate::ChatTokenizer::default(),
+    );
+
+    insta::assert_snapshot!(tokenized, @r###"
+    Hey     @cole  ,     see     https://example.com/a?b=1     👍🏽  \n  mail     name@example.com     in     #general  !
+    ╰─╯WORD
+         ╰SPACE
+            ╰───╯MENTION
+                   ╰PUNC
+                      ╰SPACE
+                         ╰─╯WORD
+                              ╰SPACE
+                                 ╰───────────────────────╯URL
+                                                            ╰SPACE
+                                                               ╰╯EMOJI
+                                                                   ╰╯NEWLINE
+                                                                       ╰──╯WORD
+                                                                             ╰SPACE
+                                                                                ╰──────────────╯EMAIL
+                                                                                                  ╰SPACE
+                                                                                                     ╰╯WORD
+                                                                                                         ╰SPACE
+                                                                                                            ╰──────╯HASHTAG
+                                                                                                                      ╰PUNC
+    "###);
+}
+
+#[test]
+fn test_position_units() {
+    use crate::ll_line::{x, PositionUnit};
+
+    #[derive(Debug)]
+    struct Mark;
+
+    let ll_line = create_line_from_input_tokens(
+        vec![
+            InputToken::text(String::from("🇫🇷 café"), Vec::new()),
+            InputToken::custom(5, Vec::new()),
+            InputToken::text(String::from("ok"), vec![AnyAttribute::new(Mark)]),
+        ],
+        |text| text.len() * 10,
+    );
+
+    let found = ll_line.find(&x::token_text());
+    let ranges: Vec<_> = [
+        PositionUnit::Custom,
+        PositionUnit::Byte,
+        PositionUnit::Char,
+        PositionUnit::Utf16,
+        PositionUnit::Grapheme,
+    ]
+    .iter()
+    .map(|unit| {
+        let ranges: Vec<_> = found.iter().map(|find| find.range_in(*unit)).collect();
+        format!("{:?}: {:?}", unit, ranges)
+    })
+    .collect();
+
+    insta::assert_snapshot!(ranges.join("\n"), @r###"
+    Custom: [(0, 80), (80, 90), (90, 140), (145, 165)]
+    Byte: [(0, 8), (8, 9), (9, 14), (14, 16)]
+    Char: [(0, 2), (2, 3), (3, 7), (7, 9)]
+    Utf16: [(0, 4), (4, 5), (5, 9), (9, 11)]
+    Grapheme: [(0, 1), (1, 2), (2, 6), (6, 8)]
+    "###);
+
+    assert_eq!(
+        ll_line.query_in::<Mark>(PositionUnit::Utf16)[0].0,
+        ll_line.find(&x::attr::<Mark>())[0].range_in(PositionUnit::Utf16)
+    );
+    assert_eq!(ll_line.query_in::<Mark>(PositionUnit::Byte)[0].0, (14, 16));
+}
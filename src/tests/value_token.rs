@@ -0,0 +1,75 @@
+use super::{LLCursorAssignment, LLLineDisplay, LLSelection, Resolver};
+use crate::create_tokens::{create_line_from_input_tokens, InputToken};
+use crate::ll_line::{x, LToken};
+
+#[derive(Debug, Clone, PartialEq)]
+struct User {
+    id: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Date(&'static str);
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Assigned(u32);
+
+/// "assign" followed by a mention
+struct AssignedResolver;
+
+impl Resolver for AssignedResolver {
+    type Attr = Assigned;
+
+    fn go(&self, selection: LLSelection) -> Vec<LLCursorAssignment<Self::Attr>> {
+        selection
+            .find_by(&x::seq((
+                x::token_text(),
+                x::whitespace(),
+                x::value::<User>(),
+            )))
+            .into_iter()
+            .filter(|(_, (text, _, _))| *text == "assign")
+            .map(|(selection, (_, _, user))| selection.finish_with_attr(Assigned(user.id)))
+            .collect()
+    }
+}
+
+#[test]
+fn test_value_tokens() {
+    let ll_line = create_line_from_input_tokens(
+        vec![
+            InputToken::text("assign ".to_string(), Vec::new()),
+            InputToken::value(1, "@cole", User { id: 7 }, Vec::new()),
+            InputToken::text(" by ".to_string(), Vec::new()),
+            InputToken::value(1, "next friday", Date("2021-06-04"), Vec::new()),
+            InputToken::text(" or ".to_string(), Vec::new()),
+            InputToken::custom(1, Vec::new()),
+        ],
+        |text| text.len(),
+    )
+    .run(&AssignedResolver);
+
+    let mut ll_line_display = LLLineDisplay::new(&ll_line);
+    ll_line_display.include::<Assigned>();
+
+    insta::assert_snapshot!(ll_line_display, @r###"
+    assign     <@cole>     by     <next friday>     or     <>
+    ╰────────────────╯Assigned(7)
+    "###);
+
+    let dates: Vec<_> = ll_line
+        .find(&x::value::<Date>())
+        .into_iter()
+        .map(|find| (find.range(), *find.attr()))
+        .collect();
+    assert_eq!(dates, vec![((12, 13), &Date("2021-06-04"))]);
+
+    match ll_line.ll_tokens()[2].get_token() {
+        LToken::Value(value) => {
+            assert_eq!(value.label(), "@cole");
+            assert_eq!(value.payload::<User>(), Some(&User { id: 7 }));
+            assert_eq!(value.payload::<Date>(), None);
+        }
+        token => panic!("expected a value token, got {:?}", token),
+    }
+}
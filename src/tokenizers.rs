@@ -0,0 +1,28 @@
+mod chat;
+mod default;
+mod get_word_tag;
+mod rules;
+mod whitespace;
+
+pub use chat::ChatTokenizer;
+pub use default::DefaultTokenizer;
+pub use rules::RuleTokenizer;
+pub use whitespace::WhitespaceTokenizer;
+
+use crate::ll_line::TextTag;
+
+/// Splits the text of every [InputToken::Text](crate::InputToken::Text) into the tokens of a line.
+///
+/// Use with [create_line_from_input_tokens_with_tokenizer](crate::create_line_from_input_tokens_with_tokenizer).
+pub trait Tokenizer {
+    /// Split `text` into tagged pieces.
+    ///
+    /// Pieces must be non-empty, and in order they must cover all of `text`.
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)>;
+}
+
+impl<T: Tokenizer + ?Sized> Tokenizer for &T {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+        (**self).tokenize(text)
+    }
+}
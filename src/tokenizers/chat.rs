@@ -0,0 +1,236 @@
+use super::rules::{name_len, prefixed_len, tokenize_with_rules};
+use super::{DefaultTokenizer, Tokenizer};
+use crate::ll_line::TextTag;
+use unicode_segmentation::UnicodeSegmentation;
+
+const URL_PREFIXES: &[&str] = &["https://", "http://", "www."];
+
+/// Like the [DefaultTokenizer], but for chat messages: urls, emails, `@mentions` and `#hashtags`
+/// are kept as single tokens, and emoji and line breaks get their own tags.
+///
+/// | Text                          | Tag                 |
+/// |-------------------------------|---------------------|
+/// | `https://example.com/a?b=1`   | [TextTag::URL]      |
+/// | `name@example.com`            | [TextTag::EMAIL]    |
+/// | `@someone`                    | [TextTag::MENTION]  |
+/// | `#general`                    | [TextTag::HASHTAG]  |
+/// | `👍🏽`, `🇫🇷`                    | [TextTag::EMOJI]    |
+/// | `\n`, `\r\n`                   | [TextTag::NEWLINE]  |
+#[derive(Default, Debug, Clone, Copy)]
+pub struct ChatTokenizer(());
+
+impl Tokenizer for ChatTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+        tokenize_with_rules(text, &DefaultTokenizer::default(), match_chat_rules)
+            .into_iter()
+            .map(|(piece, tag)| match tag {
+                TextTag::SPACE if is_newlines(piece) => (piece, TextTag::NEWLINE),
+                TextTag::SYMB if is_emoji(piece) => (piece, TextTag::EMOJI),
+                tag => (piece, tag),
+            })
+            .collect()
+    }
+}
+
+/// Rules of the [ChatTokenizer] recognizing pieces of chat messages
+/// which unicode word boundaries would split apart.
+fn match_chat_rules(text: &str, idx: usize) -> Option<(usize, TextTag)> {
+    url_len(text, idx)
+        .map(|len| (len, TextTag::URL))
+        .or_else(|| email_len(text, idx).map(|len| (len, TextTag::EMAIL)))
+        .or_else(|| named_len(text, idx, '@').map(|len| (len, TextTag::MENTION)))
+        .or_else(|| named_len(text, idx, '#').map(|len| (len, TextTag::HASHTAG)))
+}
+
+/// `https://example.com/path?query`, up to the next whitespace
+fn url_len(text: &str, idx: usize) -> Option<usize> {
+    let rest = &text[idx..];
+    let prefix = URL_PREFIXES.iter().find(|prefix| {
+        rest.get(..prefix.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+    })?;
+
+    if follows(text, idx, char::is_alphanumeric) {
+        return None;
+    }
+
+    let mut url = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+    // Trailing punctuation is more likely to be part of the sentence than of the url
+    loop {
+        let trimmed = url.trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', '"']);
+        let trimmed = match trimmed.strip_suffix(')') {
+            Some(without_paren) if trimmed.matches('(').count() < trimmed.matches(')').count() => {
+                without_paren
+            }
+            _ => trimmed,
+        };
+
+        if trimmed.len() == url.len() {
+            break;
+        }
+        url = trimmed;
+    }
+
+    if url.len() > prefix.len() {
+        Some(url.len())
+    } else {
+        None
+    }
+}
+
+/// `name.last+tag@example.com`
+fn email_len(text: &str, idx: usize) -> Option<usize> {
+    if follows(text, idx, is_email_local_char) {
+        return None;
+    }
+
+    let rest = &text[idx..];
+    let local_len: usize = rest
+        .chars()
+        .take_while(|c| is_email_local_char(*c))
+        .map(char::len_utf8)
+        .sum();
+    if local_len == 0 || !rest[local_len..].starts_with('@') {
+        return None;
+    }
+
+    let domain_start = local_len + '@'.len_utf8();
+    let domain = &rest[domain_start..domain_start + name_len(&rest[domain_start..])];
+    let top_level_domain = domain.rsplit('.').next().unwrap_or_default();
+    if domain.contains('.')
+        && top_level_domain.chars().count() >= 2
+        && top_level_domain.chars().all(char::is_alphabetic)
+    {
+        Some(domain_start + domain.len())
+    } else {
+        None
+    }
+}
+
+/// `@someone` or `#general`, where the name has at least one letter
+fn named_len(text: &str, idx: usize, prefix: char) -> Option<usize> {
+    let len = prefixed_len(text, idx, prefix)?;
+    if text[idx + prefix.len_utf8()..idx + len]
+        .chars()
+        .any(char::is_alphabetic)
+    {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+fn is_line_break(c: char) -> bool {
+    matches!(
+        c,
+        '\n' | '\r' | '\u{0B}' | '\u{0C}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+fn is_newlines(input: &str) -> bool {
+    !input.is_empty() && input.chars().all(is_line_break)
+}
+
+/// Approximates emoji with the main emoji blocks, or with the emoji presentation
+/// selector and keycap characters for the symbols which aren't emoji by default.
+fn is_emoji(input: &str) -> bool {
+    !input.is_empty()
+        && input.graphemes(true).all(|grapheme| {
+            grapheme.chars().next().is_some_and(
+                |first| matches!(first, '\u{1F000}'..='\u{1FAFF}' | '\u{2600}'..='\u{27BF}'),
+            ) || grapheme.contains(['\u{FE0F}', '\u{20E3}'])
+        })
+}
+
+fn is_email_local_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '.' | '_' | '%' | '+' | '-')
+}
+
+fn follows<P: Fn(char) -> bool>(text: &str, idx: usize, predicate: P) -> bool {
+    text[..idx].chars().next_back().is_some_and(predicate)
+}
+
+#[cfg(test)]
+mod tests {
+    fn matched(text: &str, len_fn: impl Fn(&str, usize) -> Option<usize>) -> Option<&str> {
+        len_fn(text, 0).map(|len| &text[..len])
+    }
+
+    #[test]
+    fn test_is_emoji() {
+        assert!(super::is_emoji("😀"));
+        assert!(super::is_emoji("🦶🏽"));
+        assert!(super::is_emoji("👨‍👩‍👧"));
+        assert!(super::is_emoji("🇫🇷"));
+        assert!(super::is_emoji("1\u{FE0F}\u{20E3}"));
+        assert!(super::is_emoji("❤\u{FE0F}"));
+        assert!(super::is_emoji("😀😀"));
+        // not
+        assert!(!super::is_emoji(""), "empty is not emoji");
+        assert!(!super::is_emoji("$"));
+        assert!(!super::is_emoji("A"));
+        assert!(!super::is_emoji("😀A"));
+    }
+
+    #[test]
+    fn test_is_newlines() {
+        assert!(super::is_newlines("\n"));
+        assert!(super::is_newlines("\r\n"));
+        assert!(super::is_newlines("\u{2028}"));
+        // not
+        assert!(!super::is_newlines(""), "empty is not newlines");
+        assert!(!super::is_newlines(" \n"));
+        assert!(!super::is_newlines("\t"));
+    }
+
+    #[test]
+    fn test_url_len() {
+        let url_len = |text| matched(text, super::url_len);
+
+        assert_eq!(url_len("https://example.com"), Some("https://example.com"));
+        assert_eq!(
+            url_len("HTTP://example.com/a?b=c#d"),
+            Some("HTTP://example.com/a?b=c#d")
+        );
+        assert_eq!(url_len("www.example.com, and"), Some("www.example.com"));
+        assert_eq!(
+            url_len("https://example.com)."),
+            Some("https://example.com")
+        );
+        assert_eq!(
+            url_len("https://en.wikipedia.org/wiki/Rust_(programming_language))"),
+            Some("https://en.wikipedia.org/wiki/Rust_(programming_language)")
+        );
+        // not
+        assert_eq!(url_len("https://"), None);
+        assert_eq!(url_len("example.com"), None);
+    }
+
+    #[test]
+    fn test_email_len() {
+        let email_len = |text| matched(text, super::email_len);
+
+        assert_eq!(email_len("name@example.com"), Some("name@example.com"));
+        assert_eq!(
+            email_len("first.last+tag@mail.example.co."),
+            Some("first.last+tag@mail.example.co")
+        );
+        // not
+        assert_eq!(email_len("name@localhost"), None);
+        assert_eq!(email_len("name@example.c0m"), None);
+        assert_eq!(email_len("@example.com"), None);
+    }
+
+    #[test]
+    fn test_named_len() {
+        let named_len =
+            |text, prefix| matched(text, |text, idx| super::named_len(text, idx, prefix));
+
+        assert_eq!(named_len("@cole.", '@'), Some("@cole"));
+        assert_eq!(named_len("#general!", '#'), Some("#general"));
+        assert_eq!(named_len("#dev-ops_2 ", '#'), Some("#dev-ops_2"));
+        // not
+        assert_eq!(named_len("#1", '#'), None);
+        assert_eq!(named_len("# general", '#'), None);
+    }
+}
@@ -0,0 +1,76 @@
+use super::{get_word_tag, Tokenizer};
+use crate::create_tokens::decimal_digit_value;
+use crate::ll_line::TextTag;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Splits text on unicode word boundaries, and then splits apart digits from the
+/// letters and punctuation they are grouped with.
+///
+/// This is the tokenizer used by [create_line_from_input_tokens](crate::create_line_from_input_tokens).
+#[derive(Default, Debug, Clone, Copy)]
+pub struct DefaultTokenizer(());
+
+impl Tokenizer for DefaultTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+        // `fold` because we end up splitting more than just unicode word boundaries
+        text.split_word_bounds()
+            .fold(Vec::new(), |mut tokens, unicode_word| {
+                // Split apart digit word boundaries, because unicode `split_word_bounds` will group digits and commas and points together
+                // such as "12,3" and "10.0". We need these to be split up further into ["12", ",", "3"] and ["10", ".", "0"] respectively.
+                // http://www.unicode.org/reports/tr29/#Word_Boundaries
+                // if \d+[,\.a-zA-Z]\d+ or more repeats (3 is minimum)
+                if unicode_word.starts_with(|c: char| decimal_digit_value(c).is_some()) {
+                    split_digit_word(unicode_word, &mut tokens);
+                } else {
+                    // Contractions like "don't" stay a single WORD, see ContractionResolver
+                    tokens.push((
+                        unicode_word,
+                        get_word_tag::get_unicode_word_tag(unicode_word),
+                    ));
+                }
+
+                tokens
+            })
+    }
+}
+
+/// Splits a word boundary starting with a digit into runs of digits ([TextTag::NATN]),
+/// runs of letters ([TextTag::WORD]), and anything else one grapheme at a time.
+fn split_digit_word<'t>(unicode_word: &'t str, tokens: &mut Vec<(&'t str, TextTag)>) {
+    // start index and tag of the run being collected
+    let mut collected: Option<(usize, TextTag)> = None;
+
+    // Iterate graphemes rather than chars so combining marks (like the ones
+    // following the digit of a keycap emoji) are never split from their base
+    for (idx, grapheme) in unicode_word.grapheme_indices(true) {
+        let mut chars = grapheme.chars();
+        let first = chars.next().expect("graphemes are never empty");
+        let is_single_char = chars.next().is_none();
+
+        let tag = if is_single_char && decimal_digit_value(first).is_some() {
+            TextTag::NATN
+        } else if first.is_alphabetic() {
+            TextTag::WORD
+        } else if is_single_char {
+            TextTag::PUNC
+        } else {
+            TextTag::SYMB
+        };
+
+        if let Some((start_idx, collected_tag)) = collected.take() {
+            if collected_tag == tag && matches!(tag, TextTag::NATN | TextTag::WORD) {
+                // continue the run
+                collected = Some((start_idx, collected_tag));
+                continue;
+            }
+
+            tokens.push((&unicode_word[start_idx..idx], collected_tag));
+        }
+
+        collected = Some((idx, tag));
+    }
+
+    if let Some((start_idx, collected_tag)) = collected {
+        tokens.push((&unicode_word[start_idx..], collected_tag));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::create_tokens::decimal_digit_value;
 use crate::ll_line::TextTag;
 
 // Hand wavy, punctuation is just the characters that can affect how a sentence is split apart
@@ -8,6 +9,8 @@ pub(crate) fn get_unicode_word_tag(word: &str) -> TextTag {
         TextTag::SPACE
     } else if word.len() == 1 && PUNCTUATION.contains(&word.chars().next().unwrap()) {
         TextTag::PUNC
+    } else if word.chars().all(|c| decimal_digit_value(c).is_some()) {
+        TextTag::NATN
     } else if is_word(word) {
         TextTag::WORD
     } else {
@@ -41,7 +44,7 @@ fn is_word(input: &str) -> bool {
         } else {
             // can be a dash or apostrophe
             match c {
-                '\'' | '-' | '–' => continue,
+                '\'' | '’' | '-' | '–' => continue,
                 _ => {
                     return false;
                 }
@@ -57,6 +60,7 @@ mod tests {
     #[test]
     fn test_is_word() {
         assert!(super::is_word("don't"));
+        assert!(super::is_word("don’t"));
         assert!(super::is_word("A"));
         assert!(super::is_word("Story"));
         assert!(super::is_word("cameras'"));
@@ -0,0 +1,197 @@
+use super::{DefaultTokenizer, Tokenizer};
+use crate::ll_line::TextTag;
+
+/// Given the whole text and the index where a token may start, returns the length of the token.
+type RuleFn = dyn Fn(&str, usize) -> Option<usize> + Send + Sync;
+
+struct TokenRule {
+    tag: TextTag,
+    matcher: Box<RuleFn>,
+}
+
+/// Tokenizer trying its rules wherever its fallback tokenizer would start a new token.
+///
+/// The first rule matching at a position creates a token; if none match, the fallback
+/// tokenizer's token is used.
+///
+/// ```
+/// use layered_nlp::{RuleTokenizer, TextTag};
+///
+/// let tokenizer = RuleTokenizer::new()
+///     // chat handles
+///     .prefixed('@', TextTag::WORD)
+///     // operators of code snippets
+///     .keep_together(&["::", "->"], TextTag::SYMB)
+///     // runs of kanji
+///     .char_run(|c| ('\u{4E00}'..='\u{9FFF}').contains(&c), TextTag::WORD);
+/// ```
+pub struct RuleTokenizer<T = DefaultTokenizer> {
+    rules: Vec<TokenRule>,
+    fallback: T,
+}
+
+impl RuleTokenizer {
+    /// Rule tokenizer falling back to the [DefaultTokenizer].
+    pub fn new() -> Self {
+        RuleTokenizer::with_fallback(DefaultTokenizer::default())
+    }
+}
+
+impl Default for RuleTokenizer {
+    fn default() -> Self {
+        RuleTokenizer::new()
+    }
+}
+
+impl<T: Tokenizer> RuleTokenizer<T> {
+    pub fn with_fallback(fallback: T) -> Self {
+        RuleTokenizer {
+            rules: Vec::new(),
+            fallback,
+        }
+    }
+
+    /// Add a custom rule.
+    ///
+    /// `matcher` receives the whole text and the index where a token may start,
+    /// and returns the length in bytes of the token to create.
+    pub fn rule<F>(mut self, tag: TextTag, matcher: F) -> Self
+    where
+        F: Fn(&str, usize) -> Option<usize> + Send + Sync + 'static,
+    {
+        self.rules.push(TokenRule {
+            tag,
+            matcher: Box::new(matcher),
+        });
+        self
+    }
+
+    /// Keep these exact strings as a single token, like `"C++"` or `"->"`.
+    ///
+    /// Longer strings are preferred when several match.
+    pub fn keep_together(self, words: &[&str], tag: TextTag) -> Self {
+        let mut words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+        words.sort_by_key(|word| std::cmp::Reverse(word.len()));
+
+        self.rule(tag, move |text, idx| {
+            words
+                .iter()
+                .find(|word| text[idx..].starts_with(word.as_str()))
+                .map(String::len)
+        })
+    }
+
+    /// Keep `prefix` followed by alphanumeric, `_`, `-` or `.` characters as a single token,
+    /// like `@someone` or `#general`.
+    ///
+    /// Doesn't match when `prefix` directly follows an alphanumeric character, as in `name@example.com`.
+    pub fn prefixed(self, prefix: char, tag: TextTag) -> Self {
+        self.rule(tag, move |text, idx| prefixed_len(text, idx, prefix))
+    }
+
+    /// Keep consecutive characters matching `predicate` as a single token.
+    pub fn char_run<P>(self, predicate: P, tag: TextTag) -> Self
+    where
+        P: Fn(char) -> bool + Send + Sync + 'static,
+    {
+        self.rule(tag, move |text, idx| {
+            let len: usize = text[idx..]
+                .chars()
+                .take_while(|c| predicate(*c))
+                .map(char::len_utf8)
+                .sum();
+            if len > 0 {
+                Some(len)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Length of the token created by the first matching rule at `idx`.
+    fn match_rules(&self, text: &str, idx: usize) -> Option<(usize, &TextTag)> {
+        self.rules.iter().find_map(|rule| {
+            (rule.matcher)(text, idx)
+                .filter(|len| *len > 0 && text.is_char_boundary(idx + len))
+                .map(|len| (len, &rule.tag))
+        })
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for RuleTokenizer<T> {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+        tokenize_with_rules(text, &self.fallback, |text, idx| {
+            self.match_rules(text, idx)
+                .map(|(len, tag)| (len, tag.clone()))
+        })
+    }
+}
+
+/// Creates a token wherever `match_rules` matches, and otherwise uses the `fallback` tokens
+/// until `match_rules` matches again at the start of one of them.
+pub(super) fn tokenize_with_rules<'t, T, R>(
+    text: &'t str,
+    fallback: &T,
+    match_rules: R,
+) -> Vec<(&'t str, TextTag)>
+where
+    T: Tokenizer + ?Sized,
+    R: Fn(&str, usize) -> Option<(usize, TextTag)>,
+{
+    let mut tokens = Vec::new();
+    let mut idx = 0;
+
+    'rules: while idx < text.len() {
+        if let Some((len, tag)) = match_rules(text, idx) {
+            tokens.push((&text[idx..idx + len], tag));
+            idx += len;
+            continue;
+        }
+
+        let mut fallback_idx = idx;
+        for (piece, tag) in fallback.tokenize(&text[idx..]) {
+            if fallback_idx > idx && match_rules(text, fallback_idx).is_some() {
+                idx = fallback_idx;
+                continue 'rules;
+            }
+
+            tokens.push((piece, tag));
+            fallback_idx += piece.len();
+        }
+
+        idx = text.len();
+    }
+
+    tokens
+}
+
+/// Length of `prefix` followed by a name (see [name_len]) at `idx`,
+/// unless `prefix` directly follows an alphanumeric character.
+pub(super) fn prefixed_len(text: &str, idx: usize, prefix: char) -> Option<usize> {
+    if !text[idx..].starts_with(prefix)
+        || text[..idx]
+            .chars()
+            .next_back()
+            .is_some_and(char::is_alphanumeric)
+    {
+        return None;
+    }
+
+    let name_len = name_len(&text[idx + prefix.len_utf8()..]);
+    if name_len > 0 {
+        Some(prefix.len_utf8() + name_len)
+    } else {
+        None
+    }
+}
+
+/// Length of the leading `[[:alnum:]_\-.]*` characters, without trailing `.` or `-`.
+pub(super) fn name_len(text: &str) -> usize {
+    let len: usize = text
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+        .map(char::len_utf8)
+        .sum();
+
+    text[..len].trim_end_matches(['.', '-']).len()
+}
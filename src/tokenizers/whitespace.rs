@@ -0,0 +1,39 @@
+use super::{get_word_tag, Tokenizer};
+use crate::ll_line::TextTag;
+
+/// Splits text into runs of whitespace and runs of anything else,
+/// so `fn main() -> Result<(), Error>` keeps `main()` and `Result<(),` as tokens.
+///
+/// Useful for code snippets, or text where punctuation is part of the words.
+#[derive(Default, Debug, Clone, Copy)]
+pub struct WhitespaceTokenizer(());
+
+impl Tokenizer for WhitespaceTokenizer {
+    fn tokenize<'t>(&self, text: &'t str) -> Vec<(&'t str, TextTag)> {
+        let mut tokens = Vec::new();
+        // start index of the run being collected, and whether it's whitespace
+        let mut collected: Option<(usize, bool)> = None;
+
+        for (idx, c) in text.char_indices() {
+            let is_whitespace = c.is_whitespace();
+            match collected {
+                Some((_, collected_whitespace)) if collected_whitespace == is_whitespace => {}
+                Some((start_idx, _)) => {
+                    tokens.push(tagged(&text[start_idx..idx]));
+                    collected = Some((idx, is_whitespace));
+                }
+                None => collected = Some((idx, is_whitespace)),
+            }
+        }
+
+        if let Some((start_idx, _)) = collected {
+            tokens.push(tagged(&text[start_idx..]));
+        }
+
+        tokens
+    }
+}
+
+fn tagged(piece: &str) -> (&str, TextTag) {
+    (piece, get_word_tag::get_unicode_word_tag(piece))
+}
@@ -145,6 +145,7 @@ trait Bucket {
     where
         Self: 'static;
     fn insert_any(&mut self, val: Box<dyn Any>);
+    fn append_any(&mut self, other: &mut dyn Any);
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.pad("Bucket")
     }
@@ -168,6 +169,9 @@ impl<T: 'static + Debug> Bucket for Vec<T> {
     fn insert_any(&mut self, val: Box<dyn Any>) {
         self.push(*val.downcast().expect("type doesn't match"));
     }
+    fn append_any(&mut self, other: &mut dyn Any) {
+        self.append(other.downcast_mut().expect("type doesn't match"));
+    }
 
     fn debug(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         Debug::fmt(self, f)
@@ -253,6 +257,27 @@ impl TypeBucket {
     //         .and_then(|boxed| boxed.downcast().ok().map(|boxed| *boxed))
     // }
 
+    /// Remove all values of the type with this `type_id`, and return them in their own `TypeBucket`.
+    pub fn remove_type(&mut self, type_id: TypeId) -> TypeBucket {
+        TypeBucket {
+            map: self.map.remove_entry(&type_id).into_iter().collect(),
+        }
+    }
+
+    /// Move all values from `other` into this `TypeBucket`, after the values already present.
+    pub fn append(&mut self, other: TypeBucket) {
+        for (type_id, mut values) in other.map {
+            match self.map.entry(type_id) {
+                hash_map::Entry::Occupied(mut entry) => {
+                    entry.get_mut().append_any(values.as_any_mut());
+                }
+                hash_map::Entry::Vacant(entry) => {
+                    entry.insert(values);
+                }
+            }
+        }
+    }
+
     /// Clear the `TypeBucket` of all inserted values.
     #[inline]
     pub fn clear(&mut self) {
@@ -310,3 +335,24 @@ fn test_type_map() {
 
     // assert_eq!(map.get(), Some(&MyType2("Hello".into())));
 }
+
+#[test]
+fn test_type_map_remove_and_append() {
+    #[derive(Debug, PartialEq)]
+    struct MyType(i32);
+
+    let mut map = TypeBucket::new();
+    map.insert(5i32);
+    map.insert(MyType(10));
+    map.insert(MyType(20));
+
+    let mut removed = map.remove_type(TypeId::of::<MyType>());
+    assert_eq!(map.get::<MyType>(), &[] as &[MyType]);
+    assert_eq!(removed.get::<MyType>(), &[MyType(10), MyType(20)]);
+
+    map.insert(MyType(5));
+    removed.insert(3i32);
+    map.append(removed);
+    assert_eq!(map.get::<MyType>(), &[MyType(5), MyType(10), MyType(20)]);
+    assert_eq!(map.get::<i32>(), &[5i32, 3i32]);
+}
@@ -6,24 +6,22 @@ pub struct TypeIdToMany<T> {
 }
 
 impl<Value> TypeIdToMany<Value> {
-    pub fn insert_distinct<Type: 'static>(&mut self, value_to_add: Value)
+    pub fn insert_any_distinct(&mut self, type_id: TypeId, value_to_add: Value)
     where
         Value: PartialEq,
     {
-        let entry = self.map.entry(TypeId::of::<Type>()).or_default();
+        let entry = self.map.entry(type_id).or_default();
 
         if !entry.contains(&value_to_add) {
             entry.push(value_to_add);
         }
     }
-    pub fn insert_any_distinct(&mut self, type_id: TypeId, value_to_add: Value)
+    pub fn remove_any(&mut self, type_id: TypeId, value_to_remove: &Value)
     where
         Value: PartialEq,
     {
-        let entry = self.map.entry(type_id).or_default();
-
-        if !entry.contains(&value_to_add) {
-            entry.push(value_to_add);
+        if let Some(entry) = self.map.get_mut(&type_id) {
+            entry.retain(|value| value != value_to_remove);
         }
     }
     pub fn get<Type: 'static>(&self) -> &[Value] {
@@ -31,4 +29,12 @@ impl<Value> TypeIdToMany<Value> {
             .get(&TypeId::of::<Type>())
             .map_or(&[], Vec::as_slice)
     }
+    pub fn get_any(&self, type_id: TypeId) -> &[Value] {
+        self.map.get(&type_id).map_or(&[], Vec::as_slice)
+    }
+    pub fn iter(&self) -> impl Iterator<Item = (TypeId, &[Value])> {
+        self.map
+            .iter()
+            .map(|(type_id, values)| (*type_id, values.as_slice()))
+    }
 }